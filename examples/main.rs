@@ -9,19 +9,11 @@ const TRIANGLE: [f32; 18] = [
 ];
 
 pub fn load_triangle_shader(context: &opengl::Context, v_code: &str, f_code: &str) -> Program {
-    let vs = context.new_shader(ShaderType::Vertex);
-    vs.source(v_code);
-    vs.compile().unwrap();
-
-    let fs = context.new_shader(ShaderType::Fragmet);
-    fs.source(f_code);
-    fs.compile().unwrap();
-
-    let program = context.new_program();
-    program.attach(&vs);
-    program.attach(&fs);
-    program.link().unwrap();
-    program
+    ProgramBuilder::new(context)
+        .vertex(v_code)
+        .fragment(f_code)
+        .build()
+        .unwrap_or_else(|err| panic!("{}", err))
 }
 
 pub fn load_triangle_buffer(context: &opengl::Context) -> Vertex {
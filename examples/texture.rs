@@ -45,7 +45,7 @@ pub fn load_buffer(context: &opengl::Context) -> Vertex {
 }
 
 pub fn load_texture(context: &opengl::Context) -> Texture {
-    let texture = context.new_texture();
+    let texture = context.new_texture(TexTarget::Tex2D);
 
     texture.bind(TexTarget::Tex2D);
     texture.set(TexParam::WrapS(Wrap::Repeat));
@@ -0,0 +1,94 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Member};
+
+/// Memory layout of a GLSL interface block.
+#[derive(Clone, Copy)]
+pub(crate) enum BlockLayout {
+    Std140,
+    Std430,
+}
+
+impl BlockLayout {
+    fn name(self) -> &'static str {
+        match self {
+            BlockLayout::Std140 => "Std140",
+            BlockLayout::Std430 => "Std430",
+        }
+    }
+
+    /// Minimum base alignment of a struct, `std140` rounds it up to the one of a `vec4`.
+    fn min_align(self) -> usize {
+        match self {
+            BlockLayout::Std140 => 16,
+            BlockLayout::Std430 => 1,
+        }
+    }
+}
+
+pub(crate) fn expand(input: DeriveInput, layout: BlockLayout) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("`{}` can only be derived for structs", layout.name()),
+            ))
+        }
+    };
+    let members: Vec<(Member, &syn::Type)> = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|field| (Member::Named(field.ident.clone().unwrap()), &field.ty))
+            .collect(),
+        Fields::Unnamed(unnamed) => unnamed
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(index, field)| (Member::Unnamed(index.into()), &field.ty))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let name = layout.name();
+    let trait_ident = format_ident!("{}", name);
+    let write = format_ident!("write_{}", name.to_lowercase());
+    let min_align = layout.min_align();
+    let types: Vec<_> = members.iter().map(|(_, ty)| ty).collect();
+    let member_idents: Vec<_> = members.iter().map(|(member, _)| member).collect();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::opengl::#trait_ident for #ident #ty_generics #where_clause {
+            const ALIGN: usize = {
+                let mut align = #min_align;
+                #(
+                    if <#types as ::opengl::#trait_ident>::ALIGN > align {
+                        align = <#types as ::opengl::#trait_ident>::ALIGN;
+                    }
+                )*
+                align
+            };
+            const SIZE: usize = {
+                let mut size = 0usize;
+                #(
+                    size = size.next_multiple_of(<#types as ::opengl::#trait_ident>::ALIGN)
+                        + <#types as ::opengl::#trait_ident>::SIZE;
+                )*
+                size.next_multiple_of(<Self as ::opengl::#trait_ident>::ALIGN)
+            };
+
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn #write(&self, bytes: &mut [u8]) {
+                let mut offset = 0usize;
+                #(
+                    offset = offset.next_multiple_of(<#types as ::opengl::#trait_ident>::ALIGN);
+                    ::opengl::#trait_ident::#write(&self.#member_idents, &mut bytes[offset..]);
+                    offset += <#types as ::opengl::#trait_ident>::SIZE;
+                )*
+            }
+        }
+    })
+}
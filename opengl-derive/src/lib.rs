@@ -0,0 +1,66 @@
+//! Derive macros for the `opengl` crate.
+//!
+//! The generated code refers to the `opengl` crate by its absolute path `::opengl`.
+
+mod block;
+mod vertex;
+
+use block::BlockLayout;
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derive `opengl::VertexLayout` for a `#[repr(C)]` struct.
+///
+/// Every field becomes one vertex attribute, the locations are assigned in
+/// declaration order. Fields can be configured by the `#[vertex(...)]` attribute:
+///
+/// - `location = N`: use location `N`, the following fields continue after the locations it takes.
+/// - `name = "..."`: name of the shader input, the field name is used by default.
+/// - `float`, `normalized`, `integer` or `double`: how the data is passed to the shader.
+///   Integer fields are kept as integers, `f32` fields as floats and `f64` fields as
+///   doubles unless specified otherwise.
+/// - `divisor = N`: advance the attribute once per `N` instances.
+/// - `position`: return this field from `VertexLayout::position()`, it must implement
+///   `opengl::VertexPosition`, e.g. `[f32; 3]`.
+/// - `transposed`: accept a square `mats` matrix, see below.
+/// - `skip`: do not create an attribute for this field, e.g. for padding.
+///
+/// `#[vertex(divisor = N)]` on the struct sets the divisor of all fields.
+/// An array of arrays `[[T; N]; M]` is a matrix of `M` columns, which matches a GLSL `mat`
+/// input. A row-major `mats` matrix takes one location per row instead, so it arrives
+/// transposed and is rejected at compile time unless the field is marked `transposed`,
+/// e.g. to use `transpose(...)` in the shader. As in GLSL, 64-bit vectors and columns
+/// of 3 or 4 components take 2 locations each.
+#[proc_macro_derive(VertexLayout, attributes(vertex))]
+pub fn derive_vertex_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    vertex::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `opengl::Std140` for a struct.
+///
+/// The fields are laid out in declaration order with the padding of the GLSL
+/// `std140` layout, the struct does not need to be `#[repr(C)]`.
+/// Every field must implement `Std140` too, e.g. `mats` vectors and matrices,
+/// arrays or other structs deriving it.
+#[proc_macro_derive(Std140)]
+pub fn derive_std140(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    block::expand(input, BlockLayout::Std140)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `opengl::Std430` for a struct.
+///
+/// Same as `#[derive(Std140)]` with the padding of the GLSL `std430` layout,
+/// which is only available to shader storage blocks.
+#[proc_macro_derive(Std430)]
+pub fn derive_std430(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    block::expand(input, BlockLayout::Std430)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,221 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{meta::ParseNestedMeta, Data, DeriveInput, Fields, LitStr, Member};
+
+/// Options parsed from `#[vertex(...)]` on a field.
+#[derive(Default)]
+struct FieldOptions {
+    name: Option<LitStr>,
+    location: Option<u32>,
+    kind: Option<TokenStream>,
+    divisor: Option<u32>,
+    position: bool,
+    transposed: bool,
+    skip: bool,
+}
+
+impl FieldOptions {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("vertex")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("location") {
+                    let value: syn::LitInt = meta.value()?.parse()?;
+                    options.location = Some(value.base10_parse()?);
+                } else if meta.path.is_ident("name") {
+                    options.name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("float") {
+                    options.set_kind(&meta, quote!(Float))?;
+                } else if meta.path.is_ident("normalized") {
+                    options.set_kind(&meta, quote!(Normalized))?;
+                } else if meta.path.is_ident("integer") {
+                    options.set_kind(&meta, quote!(Integer))?;
+                } else if meta.path.is_ident("double") {
+                    options.set_kind(&meta, quote!(Double))?;
+                } else if meta.path.is_ident("divisor") {
+                    options.divisor = Some(parse_divisor(&meta)?);
+                } else if meta.path.is_ident("position") {
+                    options.position = true;
+                } else if meta.path.is_ident("transposed") {
+                    options.transposed = true;
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error("unknown vertex attribute option"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+
+    fn set_kind(&mut self, meta: &ParseNestedMeta, kind: TokenStream) -> syn::Result<()> {
+        if self.kind.is_some() {
+            return Err(meta.error("the attribute kind is specified more than once"));
+        }
+        self.kind = Some(quote!(::opengl::AttribKind::#kind));
+        Ok(())
+    }
+}
+
+/// Options parsed from `#[vertex(...)]` on the struct.
+#[derive(Default)]
+struct StructOptions {
+    divisor: u32,
+}
+
+impl StructOptions {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("vertex")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("divisor") {
+                    options.divisor = parse_divisor(&meta)?;
+                } else {
+                    return Err(meta.error("unknown vertex layout option"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+fn parse_divisor(meta: &ParseNestedMeta) -> syn::Result<u32> {
+    let value: syn::LitInt = meta.value()?.parse()?;
+    value.base10_parse()
+}
+
+/// Return an error unless the struct is declared with `#[repr(C)]`.
+pub(crate) fn check_repr_c(input: &DeriveInput) -> syn::Result<()> {
+    let mut repr_c = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            }
+            Ok(())
+        })?;
+    }
+    if repr_c {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            &input.ident,
+            "the struct must be declared with `#[repr(C)]`",
+        ))
+    }
+}
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    check_repr_c(&input)?;
+    let struct_options = StructOptions::parse(&input.attrs)?;
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`VertexLayout` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut attributes = Vec::new();
+    // Matrices take several locations, so the next location is a constant expression.
+    let mut location = quote!(0u32);
+    let members: Vec<(Member, &syn::Field)> = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|field| (Member::Named(field.ident.clone().unwrap()), field))
+            .collect(),
+        Fields::Unnamed(unnamed) => unnamed
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(index, field)| (Member::Unnamed(index.into()), field))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+    let mut position = None;
+    for (member, field) in members {
+        let options = FieldOptions::parse(&field.attrs)?;
+        if options.position {
+            if position.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "only one field can be the position",
+                ));
+            }
+            position = Some(member.clone());
+        }
+        if options.skip {
+            continue;
+        }
+        if let Some(explicit) = options.location {
+            location = quote!(#explicit);
+        }
+        let name = options.name.unwrap_or_else(|| match &member {
+            Member::Named(ident) => LitStr::new(&ident.to_string(), ident.span()),
+            Member::Unnamed(index) => LitStr::new(&index.index.to_string(), index.span),
+        });
+        let ty = &field.ty;
+        let kind = options
+            .kind
+            .unwrap_or_else(|| quote!(<#ty as ::opengl::VertexAttrib>::KIND));
+        let divisor = options.divisor.unwrap_or(struct_options.divisor);
+        // Evaluated with `ATTRIBUTES`, so a row-major matrix fails to compile unless opted in.
+        let transposed = if options.transposed {
+            quote!()
+        } else {
+            let message = LitStr::new(
+                &format!(
+                    "the row-major matrix `{}` arrives transposed in the shader, \
+                     mark it by `#[vertex(transposed)]` or use an array of columns",
+                    name.value()
+                ),
+                name.span(),
+            );
+            quote!(::core::assert!(!<#ty as ::opengl::VertexAttrib>::TRANSPOSED, #message);)
+        };
+        attributes.push(quote! {
+            ::opengl::VertexAttribute {
+                name: #name,
+                location: #location,
+                locations: {
+                    #transposed
+                    <#ty as ::opengl::VertexAttrib>::LOCATIONS
+                },
+                ty: <#ty as ::opengl::VertexAttrib>::TYPE,
+                count: <#ty as ::opengl::VertexAttrib>::COUNT,
+                kind: #kind,
+                offset: ::core::mem::offset_of!(Self, #member),
+                divisor: #divisor,
+            }
+        });
+        location = quote!(#location + <#ty as ::opengl::VertexAttrib>::LOCATIONS);
+    }
+
+    let position = position.map(|member| {
+        quote! {
+            #[inline]
+            fn position(&self) -> ::core::option::Option<[f32; 3]> {
+                ::core::option::Option::Some(::opengl::VertexPosition::to_position(&self.#member))
+            }
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::opengl::VertexLayout for #ident #ty_generics #where_clause {
+            const ATTRIBUTES: &'static [::opengl::VertexAttribute] = &[#(#attributes),*];
+
+            #position
+        }
+    })
+}
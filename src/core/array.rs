@@ -1,7 +1,7 @@
-use std::ops::{Index, IndexMut};
-
 use gl::types::GLuint;
 
+use super::{AttribKind, Buffer, GlObject, GlType, Objects, VertexAttribute, VertexLayout};
+
 /// Vertex Array Object
 pub struct Array {
     array: GLuint,
@@ -12,7 +12,7 @@ impl Array {
     pub(super) fn new() -> Self {
         let mut array = 0;
         unsafe {
-            gl::GenVertexArrays(1, &mut array);
+            gl::CreateVertexArrays(1, &mut array);
         }
         Self { array }
     }
@@ -27,105 +27,201 @@ impl Drop for Array {
     }
 }
 
-/// Vertex Array Objects
-pub struct Arrays {
-    arrays: Vec<Array>,
-}
+impl GlObject for Array {
+    type Target = ();
 
-impl Arrays {
     #[inline]
-    pub(super) fn new(count: usize) -> Self {
-        let mut arrays = Vec::with_capacity(count);
-        unsafe {
-            gl::GenVertexArrays(count as i32, arrays.as_mut_ptr());
-        }
-        let arrays = arrays.into_iter().map(|array| Array { array }).collect();
-        Self { arrays }
+    unsafe fn create(_: (), ids: &mut [GLuint]) {
+        gl::CreateVertexArrays(ids.len() as _, ids.as_mut_ptr());
     }
 
-    /// Return an iterator of the arrays.
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &Array> {
-        self.arrays.iter()
+    unsafe fn delete(ids: &[GLuint]) {
+        gl::DeleteVertexArrays(ids.len() as _, ids.as_ptr());
     }
 
-    /// Return an iterator of the arrays.
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Array> {
-        self.arrays.iter_mut()
+    unsafe fn from_id(array: GLuint) -> Self {
+        Self { array }
     }
 
-    /// Return the number of arrays.
     #[inline]
-    pub fn count(&self) -> usize {
-        self.arrays.len()
+    fn id(&self) -> GLuint {
+        self.array
     }
 }
 
-impl Index<usize> for Arrays {
-    type Output = Array;
+/// Vertex Array Objects
+pub type Arrays = Objects<Array>;
 
+impl Array {
+    /// Bind this array in the current context.
     #[inline]
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.arrays[index]
+    pub fn bind(&self) {
+        unsafe {
+            gl::BindVertexArray(self.array);
+        }
     }
-}
 
-impl IndexMut<usize> for Arrays {
+    /// Unbind this array in the current context.
     #[inline]
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.arrays[index]
+    pub fn unbind() {
+        unsafe {
+            gl::BindVertexArray(0);
+        }
     }
 }
 
-pub struct IntoIter {
-    arrays: Vec<Option<Array>>,
-    index: usize,
-}
+impl Array {
+    /// Attach `buffer` to the vertex buffer binding point `binding`.
+    ///
+    /// Wrapper of `glVertexArrayVertexBuffer(...)`
+    #[inline]
+    pub fn vertex_buffer(&self, binding: u32, buffer: &Buffer, offset: usize, stride: usize) {
+        unsafe {
+            gl::VertexArrayVertexBuffer(self.array, binding, buffer.id(), offset as _, stride as _);
+        }
+    }
 
-impl IntoIter {
+    /// Describe the format of the attribute at `location`.
+    ///
+    /// Wrapper of `glVertexArrayAttribFormat(...)`
     #[inline]
-    fn new(arrays: Vec<Array>) -> Self {
-        let arrays = arrays.into_iter().map(|array| Some(array)).collect();
-        Self { arrays, index: 0 }
+    pub fn attrib_format(
+        &self,
+        location: u32,
+        ty: GlType,
+        count: usize,
+        normalized: bool,
+        relative_offset: usize,
+    ) {
+        unsafe {
+            gl::VertexArrayAttribFormat(
+                self.array,
+                location,
+                count as _,
+                ty.to_gl_type(),
+                normalized as _,
+                relative_offset as _,
+            );
+        }
+    }
+
+    /// Describe the format of the integer attribute at `location`.
+    ///
+    /// Wrapper of `glVertexArrayAttribIFormat(...)`
+    #[inline]
+    pub fn attrib_i_format(&self, location: u32, ty: GlType, count: usize, relative_offset: usize) {
+        unsafe {
+            gl::VertexArrayAttribIFormat(
+                self.array,
+                location,
+                count as _,
+                ty.to_gl_type(),
+                relative_offset as _,
+            );
+        }
     }
-}
 
-impl Iterator for IntoIter {
-    type Item = Array;
+    /// Describe the format of the double attribute at `location`.
+    ///
+    /// Wrapper of `glVertexArrayAttribLFormat(...)`
+    #[inline]
+    pub fn attrib_l_format(&self, location: u32, ty: GlType, count: usize, relative_offset: usize) {
+        unsafe {
+            gl::VertexArrayAttribLFormat(
+                self.array,
+                location,
+                count as _,
+                ty.to_gl_type(),
+                relative_offset as _,
+            );
+        }
+    }
 
+    /// Read the attribute at `location` from the vertex buffer binding point `binding`.
+    ///
+    /// Wrapper of `glVertexArrayAttribBinding(...)`
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        let result = self.arrays.get_mut(self.index)?.take();
-        self.index += 1;
-        result
+    pub fn attrib_binding(&self, location: u32, binding: u32) {
+        unsafe {
+            gl::VertexArrayAttribBinding(self.array, location, binding);
+        }
     }
-}
 
-impl IntoIterator for Arrays {
-    type Item = Array;
-    type IntoIter = IntoIter;
+    /// Advance the vertex buffer binding point `binding` once per `divisor` instances.
+    ///
+    /// Wrapper of `glVertexArrayBindingDivisor(...)`
+    #[inline]
+    pub fn binding_divisor(&self, binding: u32, divisor: u32) {
+        unsafe {
+            gl::VertexArrayBindingDivisor(self.array, binding, divisor);
+        }
+    }
 
+    /// Use `buffer` as the element buffer of this array.
+    ///
+    /// Wrapper of `glVertexArrayElementBuffer(...)`
     #[inline]
-    fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter::new(self.arrays)
+    pub fn element_buffer(&self, buffer: &Buffer) {
+        unsafe {
+            gl::VertexArrayElementBuffer(self.array, buffer.id());
+        }
     }
-}
 
-impl Array {
-    /// Bind this array in the current context.
+    /// Wrapper of `glEnableVertexArrayAttrib(...)`
     #[inline]
-    pub fn bind(&self) {
+    pub fn enable_attrib(&self, location: u32) {
         unsafe {
-            gl::BindVertexArray(self.array);
+            gl::EnableVertexArrayAttrib(self.array, location);
         }
     }
 
-    /// Unbind this array in the current context.
+    /// Wrapper of `glDisableVertexArrayAttrib(...)`
     #[inline]
-    pub fn unbind() {
+    pub fn disable_attrib(&self, location: u32) {
         unsafe {
-            gl::BindVertexArray(0);
+            gl::DisableVertexArrayAttrib(self.array, location);
+        }
+    }
+
+    /// Describe, enable and connect all `attributes` to the vertex buffer binding point `binding`.
+    ///
+    /// The divisor belongs to the binding point here, so all `attributes` must share one.
+    ///
+    /// # Panics
+    /// If the divisors of `attributes` differ, split them onto separate binding points instead.
+    #[inline]
+    pub fn attributes(&self, binding: u32, attributes: &[VertexAttribute]) {
+        if let Some(first) = attributes.first() {
+            assert!(
+                attributes.iter().all(|a| a.divisor == first.divisor),
+                "The attributes of one binding point must share the divisor"
+            );
+        }
+        for attribute in attributes {
+            let (ty, count) = (attribute.ty, attribute.count);
+            for (location, offset) in attribute.slots() {
+                match attribute.kind {
+                    AttribKind::Float => self.attrib_format(location, ty, count, false, offset),
+                    AttribKind::Normalized => self.attrib_format(location, ty, count, true, offset),
+                    AttribKind::Integer => self.attrib_i_format(location, ty, count, offset),
+                    AttribKind::Double => self.attrib_l_format(location, ty, count, offset),
+                }
+                self.attrib_binding(location, binding);
+                self.enable_attrib(location);
+            }
         }
+        if let Some(first) = attributes.first() {
+            self.binding_divisor(binding, first.divisor);
+        }
+    }
+
+    /// Set up the attributes of the vertex type `V` on the vertex buffer binding point `binding`.
+    ///
+    /// Buffers holding `V` can then be attached by `vertex_buffer(binding, buffer, offset, V::STRIDE)`.
+    #[inline]
+    pub fn layout<V: VertexLayout>(&self, binding: u32) {
+        self.attributes(binding, V::ATTRIBUTES);
     }
 }
@@ -1,8 +1,8 @@
-use std::ops::{Index, IndexMut};
+use std::{ops::Range, os::raw::c_void};
 
 use gl::types::GLuint;
 
-use super::{enums::*, GlTypeT};
+use super::{enums::*, GlClearT, GlObject, GlTypeT, Objects, VertexAttribute, VertexLayout};
 
 /// Vertex Buffer Object
 pub struct Buffer {
@@ -14,7 +14,7 @@ impl Buffer {
     pub(super) fn new() -> Self {
         let mut buffer = 0;
         unsafe {
-            gl::GenBuffers(1, &mut buffer);
+            gl::CreateBuffers(1, &mut buffer);
         }
         Self { buffer }
     }
@@ -29,103 +29,85 @@ impl Drop for Buffer {
     }
 }
 
-/// Vertex Buffer Objects
-pub struct Buffers {
-    buffers: Vec<Buffer>,
-}
+impl GlObject for Buffer {
+    type Target = ();
 
-impl Buffers {
     #[inline]
-    pub(super) fn new(count: usize) -> Self {
-        assert!(count > 0, "The number of buffers must be greater than 0");
-        let mut buffers = Vec::with_capacity(count);
-        unsafe {
-            gl::GenBuffers(count as _, buffers.as_mut_ptr());
-        }
-        let buffers = buffers
-            .into_iter()
-            .map(|buffer| Buffer { buffer })
-            .collect();
-        Self { buffers }
+    unsafe fn create(_: (), ids: &mut [GLuint]) {
+        gl::CreateBuffers(ids.len() as _, ids.as_mut_ptr());
     }
 
-    /// Return an iterator of the buffers.
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &Buffer> {
-        self.buffers.iter()
+    unsafe fn delete(ids: &[GLuint]) {
+        gl::DeleteBuffers(ids.len() as _, ids.as_ptr());
     }
 
-    /// Return an iterator of the buffers.
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Buffer> {
-        self.buffers.iter_mut()
-    }
-}
-
-pub struct IntoIter {
-    buffers: Vec<Option<Buffer>>,
-    index: usize,
-}
-
-impl IntoIter {
-    #[inline]
-    fn new(buffers: Vec<Buffer>) -> Self {
-        let buffers = buffers.into_iter().map(|buffer| Some(buffer)).collect();
-        Self { buffers, index: 0 }
+    unsafe fn from_id(buffer: GLuint) -> Self {
+        Self { buffer }
     }
-}
-
-impl Iterator for IntoIter {
-    type Item = Buffer;
 
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        let result = self.buffers.get_mut(self.index)?.take();
-        self.index += 1;
-        result
+    fn id(&self) -> GLuint {
+        self.buffer
     }
 }
 
-impl IntoIterator for Buffers {
-    type Item = Buffer;
-    type IntoIter = IntoIter;
+/// Vertex Buffer Objects
+pub type Buffers = Objects<Buffer>;
 
+impl Buffer {
+    /// Bind this buffer in the current context.
     #[inline]
-    fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter::new(self.buffers)
+    pub fn bind(&self, target: Target) {
+        unsafe {
+            gl::BindBuffer(target.to_gl_target(), self.buffer);
+        }
     }
-}
-
-impl Index<usize> for Buffers {
-    type Output = Buffer;
 
+    /// Unbind this buffer in the current context.
     #[inline]
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.buffers[index]
+    pub fn unbind(target: Target) {
+        unsafe {
+            gl::BindBuffer(target.to_gl_target(), 0);
+        }
     }
-}
 
-impl IndexMut<usize> for Buffers {
+    /// Bind this buffer to the binding point `index` of `target`.
+    ///
+    /// Wrapper of `glBindBufferBase(...)`
     #[inline]
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.buffers[index]
+    pub fn bind_base(&self, target: IndexedTarget, index: u32) {
+        unsafe {
+            gl::BindBufferBase(target.to_gl_target(), index, self.buffer);
+        }
     }
-}
 
-impl Buffer {
-    /// Bind this buffer in the current context.
+    /// Bind the bytes `range` of this buffer to the binding point `index` of `target`.
+    ///
+    /// Wrapper of `glBindBufferRange(...)`
     #[inline]
-    pub fn bind(&self, target: Target) {
+    pub fn bind_range(&self, target: IndexedTarget, index: u32, range: Range<usize>) {
+        assert!(
+            range.start <= range.end,
+            "The start of the range is after its end"
+        );
         unsafe {
-            gl::BindBuffer(target.to_gl_target(), self.buffer);
+            gl::BindBufferRange(
+                target.to_gl_target(),
+                index,
+                self.buffer,
+                range.start as _,
+                range.len() as _,
+            );
         }
     }
 
-    /// Unbind this buffer in the current context.
+    /// Unbind the binding point `index` of `target`.
     #[inline]
-    pub fn unbind(target: Target) {
+    pub fn unbind_base(target: IndexedTarget, index: u32) {
         unsafe {
-            gl::BindBuffer(target.to_gl_target(), 0);
+            gl::BindBufferBase(target.to_gl_target(), index, 0);
         }
     }
 
@@ -156,13 +138,14 @@ impl Buffer {
 
     /// Mark the vertex attribute of buffer data by `composition`.
     ///
-    /// The normalized is false by default.
+    /// The normalized is false by default, and integer data is converted to float.
+    /// Use `gen_mark_with_kind(...)` for integer or double inputs of the shader.
     #[inline]
     pub fn gen_mark(composition: &[(GlType, usize)]) {
         let mut current_pos = 0;
         let stride: usize = composition
             .iter()
-            .map(|&(ty, count)| ty.size() * count)
+            .map(|&(ty, count)| ty.attrib_size(count))
             .sum();
         for (index, &(ty, count)) in composition.iter().enumerate() {
             unsafe {
@@ -176,7 +159,7 @@ impl Buffer {
                 );
                 gl::EnableVertexAttribArray(index as _);
             }
-            current_pos += ty.size() * count;
+            current_pos += ty.attrib_size(count);
         }
     }
 
@@ -186,7 +169,7 @@ impl Buffer {
         let mut current_pos = 0;
         let stride: usize = composition
             .iter()
-            .map(|&(ty, count, _)| ty.size() * count)
+            .map(|&(ty, count, _)| ty.attrib_size(count))
             .sum();
         for (index, &(ty, count, normalized)) in composition.iter().enumerate() {
             unsafe {
@@ -200,10 +183,159 @@ impl Buffer {
                 );
                 gl::EnableVertexAttribArray(index as _);
             }
-            current_pos += ty.size() * count;
+            current_pos += ty.attrib_size(count);
+        }
+    }
+
+    /// Mark the vertex attribute of buffer data which is kept as integer in the shader.
+    ///
+    /// Wrapper of `glVertexAttribIPointer(...)`
+    ///
+    /// # Safety
+    /// A vertex array object and the buffer holding the data must be bound.
+    #[allow(private_bounds)]
+    #[inline]
+    pub unsafe fn mark_integer<T: Copy + GlTypeT>(
+        index: usize,
+        count: usize,
+        stride: usize,
+        offset: usize,
+    ) {
+        gl::VertexAttribIPointer(
+            index as _,
+            count as _,
+            T::to_gl_type(),
+            stride as _,
+            offset as _,
+        );
+        gl::EnableVertexAttribArray(index as _);
+    }
+
+    /// Mark the vertex attribute of buffer data which is kept as double in the shader.
+    ///
+    /// Wrapper of `glVertexAttribLPointer(...)`
+    ///
+    /// # Safety
+    /// A vertex array object and the buffer holding the data must be bound.
+    #[inline]
+    pub unsafe fn mark_double(index: usize, count: usize, stride: usize, offset: usize) {
+        gl::VertexAttribLPointer(index as _, count as _, gl::DOUBLE, stride as _, offset as _);
+        gl::EnableVertexAttribArray(index as _);
+    }
+
+    /// Mark the vertex attribute of buffer data by `composition`,
+    /// each attribute states how it is passed to the shader.
+    #[inline]
+    pub fn gen_mark_with_kind(composition: &[(GlType, usize, AttribKind)]) {
+        let mut current_pos = 0;
+        let stride: usize = composition
+            .iter()
+            .map(|&(ty, count, _)| ty.attrib_size(count))
+            .sum();
+        for (index, &(ty, count, kind)) in composition.iter().enumerate() {
+            unsafe {
+                Self::attrib_pointer(index as _, ty, count, kind, stride, current_pos);
+            }
+            current_pos += ty.attrib_size(count);
+        }
+    }
+
+    /// Mark the per-instance vertex attributes of buffer data by `composition`.
+    ///
+    /// The attributes take the locations starting from `first_index`
+    /// and advance once per `divisor` instances.
+    #[inline]
+    pub fn gen_mark_instanced(
+        first_index: usize,
+        divisor: u32,
+        composition: &[(GlType, usize, AttribKind)],
+    ) {
+        let mut current_pos = 0;
+        let stride: usize = composition
+            .iter()
+            .map(|&(ty, count, _)| ty.attrib_size(count))
+            .sum();
+        for (index, &(ty, count, kind)) in composition.iter().enumerate() {
+            let location = (first_index + index) as _;
+            unsafe {
+                Self::attrib_pointer(location, ty, count, kind, stride, current_pos);
+                gl::VertexAttribDivisor(location, divisor);
+            }
+            current_pos += ty.attrib_size(count);
+        }
+    }
+
+    /// Wrapper of `glVertexAttribDivisor(...)`
+    #[inline]
+    pub fn divisor(index: usize, divisor: u32) {
+        unsafe {
+            gl::VertexAttribDivisor(index as _, divisor);
+        }
+    }
+
+    /// Mark the vertex attributes described by `attributes`,
+    /// each vertex occupies `stride` bytes.
+    #[inline]
+    pub fn mark_attributes(attributes: &[VertexAttribute], stride: usize) {
+        for attribute in attributes {
+            for (location, offset) in attribute.slots() {
+                unsafe {
+                    Self::attrib_pointer(
+                        location,
+                        attribute.ty,
+                        attribute.count,
+                        attribute.kind,
+                        stride,
+                        offset,
+                    );
+                    gl::VertexAttribDivisor(location, attribute.divisor);
+                }
+            }
         }
     }
 
+    #[inline]
+    unsafe fn attrib_pointer(
+        location: u32,
+        ty: GlType,
+        count: usize,
+        kind: AttribKind,
+        stride: usize,
+        offset: usize,
+    ) {
+        match kind {
+            AttribKind::Float | AttribKind::Normalized => gl::VertexAttribPointer(
+                location,
+                count as _,
+                ty.to_gl_type(),
+                (kind == AttribKind::Normalized) as _,
+                stride as _,
+                offset as _,
+            ),
+            AttribKind::Integer => gl::VertexAttribIPointer(
+                location,
+                count as _,
+                ty.to_gl_type(),
+                stride as _,
+                offset as _,
+            ),
+            AttribKind::Double => gl::VertexAttribLPointer(
+                location,
+                count as _,
+                ty.to_gl_type(),
+                stride as _,
+                offset as _,
+            ),
+        }
+        gl::EnableVertexAttribArray(location);
+    }
+
+    /// Mark the vertex attributes of the vertex type `V`.
+    #[inline]
+    pub fn mark_layout<V: VertexLayout>() {
+        Self::mark_attributes(V::ATTRIBUTES, V::STRIDE);
+    }
+
     /// Upload `data` to this buffer.
     #[inline]
     pub fn data<'a, 'b: 'a, T: Copy>(&'a self, data: &'b [T], usage: Usage) {
@@ -230,3 +362,162 @@ impl Buffer {
         }
     }
 }
+
+impl Buffer {
+    /// Wrapper of `glNamedBufferStorage(...)`
+    ///
+    /// Allocate `size` bytes of immutable storage for this buffer.
+    /// `flags` is a combination of the bits in `BufferFlag`.
+    #[inline]
+    pub fn storage(&self, size: usize, flags: u32) {
+        unsafe {
+            gl::NamedBufferStorage(self.buffer, size as _, std::ptr::null(), flags);
+        }
+    }
+
+    /// Wrapper of `glNamedBufferStorage(...)`
+    ///
+    /// Allocate immutable storage for this buffer and initialize it with `data`.
+    #[inline]
+    pub fn storage_with<T: Copy>(&self, data: &[T], flags: u32) {
+        unsafe {
+            gl::NamedBufferStorage(
+                self.buffer,
+                size_of_val(data) as _,
+                data.as_ptr() as _,
+                flags,
+            );
+        }
+    }
+
+    /// Wrapper of `glMapNamedBufferRange(...)`
+    ///
+    /// Return a null pointer if the range cannot be mapped.
+    ///
+    /// # Safety
+    /// The returned pointer is only valid until the buffer is unmapped or deleted,
+    /// and must only be accessed in the way allowed by `access`.
+    #[inline]
+    pub unsafe fn map_range(&self, offset: usize, length: usize, access: u32) -> *mut c_void {
+        gl::MapNamedBufferRange(self.buffer, offset as _, length as _, access)
+    }
+
+    /// Wrapper of `glFlushMappedNamedBufferRange(...)`
+    #[inline]
+    pub fn flush_range(&self, offset: usize, length: usize) {
+        unsafe {
+            gl::FlushMappedNamedBufferRange(self.buffer, offset as _, length as _);
+        }
+    }
+
+    /// Wrapper of `glUnmapNamedBuffer(...)`
+    ///
+    /// It will return `false` if the data store contents have become corrupt
+    /// while the buffer was mapped.
+    #[inline]
+    pub fn unmap(&self) -> bool {
+        unsafe { gl::UnmapNamedBuffer(self.buffer) == gl::TRUE }
+    }
+}
+
+impl Buffer {
+    /// Wrapper of `glGetNamedBufferParameteri64v(GL_BUFFER_SIZE, ...)`
+    #[inline]
+    pub fn size(&self) -> usize {
+        let mut size = 0;
+        unsafe {
+            gl::GetNamedBufferParameteri64v(self.buffer, gl::BUFFER_SIZE, &mut size);
+        }
+        size as usize
+    }
+
+    /// Read the data of this buffer starting from `offset` into `data`.
+    ///
+    /// Wrapper of `glGetNamedBufferSubData(...)`
+    ///
+    /// # Safety
+    /// The bytes stored in the buffer must be a valid representation of `T`.
+    #[inline]
+    pub unsafe fn read_into<T: Copy>(&self, offset: usize, data: &mut [T]) {
+        gl::GetNamedBufferSubData(
+            self.buffer,
+            offset as _,
+            size_of_val(data) as _,
+            data.as_mut_ptr() as _,
+        );
+    }
+
+    /// Copy `size` bytes from this buffer to `dst`.
+    ///
+    /// Wrapper of `glCopyNamedBufferSubData(...)`
+    #[inline]
+    pub fn copy_to(&self, dst: &Buffer, src_offset: usize, dst_offset: usize, size: usize) {
+        unsafe {
+            gl::CopyNamedBufferSubData(
+                self.buffer,
+                dst.buffer,
+                src_offset as _,
+                dst_offset as _,
+                size as _,
+            );
+        }
+    }
+
+    /// Fill the whole buffer with `value`.
+    ///
+    /// Wrapper of `glClearNamedBufferData(...)`
+    #[allow(private_bounds)]
+    #[inline]
+    pub fn clear_with<T: GlClearT>(&self, value: T) {
+        let (internal_format, format) = T::to_gl_clear_format();
+        unsafe {
+            gl::ClearNamedBufferData(
+                self.buffer,
+                internal_format,
+                format,
+                T::to_gl_type(),
+                &value as *const T as _,
+            );
+        }
+    }
+
+    /// Fill `size` bytes of this buffer starting from `offset` with `value`.
+    ///
+    /// Wrapper of `glClearNamedBufferSubData(...)`
+    #[allow(private_bounds)]
+    #[inline]
+    pub fn clear_range_with<T: GlClearT>(&self, offset: usize, size: usize, value: T) {
+        let (internal_format, format) = T::to_gl_clear_format();
+        unsafe {
+            gl::ClearNamedBufferSubData(
+                self.buffer,
+                internal_format,
+                offset as _,
+                size as _,
+                format,
+                T::to_gl_type(),
+                &value as *const T as _,
+            );
+        }
+    }
+
+    /// Orphan the data store of this buffer.
+    ///
+    /// Wrapper of `glInvalidateBufferData(...)`
+    #[inline]
+    pub fn invalidate(&self) {
+        unsafe {
+            gl::InvalidateBufferData(self.buffer);
+        }
+    }
+
+    /// Invalidate `length` bytes of this buffer starting from `offset`.
+    ///
+    /// Wrapper of `glInvalidateBufferSubData(...)`
+    #[inline]
+    pub fn invalidate_range(&self, offset: usize, length: usize) {
+        unsafe {
+            gl::InvalidateBufferSubData(self.buffer, offset as _, length as _);
+        }
+    }
+}
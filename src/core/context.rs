@@ -3,6 +3,17 @@ use super::{Texture, Textures};
 use super::enums::*;
 use std::{os::raw::c_void, ptr::null};
 
+/// Layout of the commands read by `Context::multi_draw_elements_indirect(...)`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DrawElementsIndirectCommand {
+    pub count: u32,
+    pub instance_count: u32,
+    pub first_index: u32,
+    pub base_vertex: i32,
+    pub base_instance: u32,
+}
+
 pub struct Context {
     _private: *const (),
 }
@@ -89,16 +100,16 @@ impl Context {
         super::Program::new()
     }
 
-    /// Create a new texture object.
+    /// Create a new texture object for `target`, which it can only be bound to.
     #[inline]
-    pub fn new_texture(&self) -> Texture {
-        Texture::new()
+    pub fn new_texture(&self, target: TexTarget) -> Texture {
+        Texture::new(target)
     }
 
-    /// Create multiple new texture objeects.
+    /// Create multiple new texture objects for `target`.
     #[inline]
-    pub fn new_textures(&self, count: usize) -> Textures {
-        Textures::new(count)
+    pub fn new_textures(&self, target: TexTarget, count: usize) -> Textures {
+        Textures::with_target(target, count)
     }
 
     /// Create a new frame buffer object.
@@ -121,6 +132,56 @@ impl Context {
         super::RenderBuffers::new(count)
     }
 
+    /// Insert a new fence sync object into the command stream.
+    #[inline]
+    pub fn new_fence(&self) -> super::Fence {
+        super::Fence::new()
+    }
+
+    /// Wrapper of `glGetIntegerv(GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT, ...)`
+    #[inline]
+    pub fn uniform_buffer_offset_alignment(&self) -> usize {
+        let mut alignment = 0;
+        unsafe {
+            gl::GetIntegerv(gl::UNIFORM_BUFFER_OFFSET_ALIGNMENT, &mut alignment);
+        }
+        alignment.max(1) as usize
+    }
+
+    /// Set the row alignment of pixel data read by texture uploads, 1, 2, 4 or 8.
+    ///
+    /// Wrapper of `glPixelStorei(GL_UNPACK_ALIGNMENT, ...)`
+    #[inline]
+    pub fn unpack_alignment(&self, alignment: u32) {
+        unsafe {
+            gl::PixelStorei(gl::UNPACK_ALIGNMENT, alignment as _);
+        }
+    }
+
+    /// Return the company responsible for the implementation.
+    ///
+    /// Wrapper of `glGetString(GL_VENDOR)`
+    #[inline]
+    pub fn vendor(&self) -> String {
+        get_string(gl::VENDOR)
+    }
+
+    /// Return the name of the renderer, usually the GPU model.
+    ///
+    /// Wrapper of `glGetString(GL_RENDERER)`
+    #[inline]
+    pub fn renderer(&self) -> String {
+        get_string(gl::RENDERER)
+    }
+
+    /// Return the version of the implementation, including the driver version.
+    ///
+    /// Wrapper of `glGetString(GL_VERSION)`
+    #[inline]
+    pub fn version(&self) -> String {
+        get_string(gl::VERSION)
+    }
+
     /// Wrapper of `glViewport(...)`.
     #[inline]
     pub fn view_port(&self, x: i32, y: i32, width: i32, height: i32) {
@@ -260,6 +321,14 @@ impl Context {
         }
     }
 
+    /// Wrapper of `glDrawArraysInstanced(...)`
+    #[inline]
+    pub fn draw_arrays_instanced(&self, mode: Mode, first: i32, count: i32, instances: i32) {
+        unsafe {
+            gl::DrawArraysInstanced(mode.to_gl_mode(), first, count, instances);
+        }
+    }
+
     /// Warpper of `glDrawElements(...)`
     #[inline]
     pub fn draw_elements(&self, mode: Mode, count: usize, ty: super::GlType, offset: usize) {
@@ -287,6 +356,80 @@ impl Context {
         gl::DrawElements(mode.to_gl_mode(), count as _, ty.to_gl_type(), offset as _);
     }
 
+    /// Wrapper of `glDrawElementsInstanced(...)`
+    #[inline]
+    pub fn draw_elements_instanced(
+        &self,
+        mode: Mode,
+        count: usize,
+        ty: super::GlType,
+        offset: usize,
+        instances: usize,
+    ) {
+        if !Self::check_type_is_indeice(ty) {
+            panic!("The type is not a index type.");
+        }
+        unsafe {
+            gl::DrawElementsInstanced(
+                mode.to_gl_mode(),
+                count as _,
+                ty.to_gl_type(),
+                offset as _,
+                instances as _,
+            );
+        }
+    }
+
+    /// Wrapper of `glDrawElementsBaseVertex(...)`
+    #[inline]
+    pub fn draw_elements_base_vertex(
+        &self,
+        mode: Mode,
+        count: usize,
+        ty: super::GlType,
+        offset: usize,
+        base_vertex: i32,
+    ) {
+        if !Self::check_type_is_indeice(ty) {
+            panic!("The type is not a index type.");
+        }
+        unsafe {
+            gl::DrawElementsBaseVertex(
+                mode.to_gl_mode(),
+                count as _,
+                ty.to_gl_type(),
+                offset as _,
+                base_vertex,
+            );
+        }
+    }
+
+    /// Wrapper of `glMultiDrawElementsIndirect(...)`
+    ///
+    /// The commands are read from the buffer bound to `Target::DrawIndirect`
+    /// starting at `offset`, laid out as `DrawElementsIndirectCommand`.
+    #[inline]
+    pub fn multi_draw_elements_indirect(
+        &self,
+        mode: Mode,
+        ty: super::GlType,
+        offset: usize,
+        draw_count: usize,
+    ) {
+        if !Self::check_type_is_indeice(ty) {
+            panic!("The type is not a index type.");
+        }
+        unsafe {
+            gl::MultiDrawElementsIndirect(
+                mode.to_gl_mode(),
+                ty.to_gl_type(),
+                offset as _,
+                draw_count as _,
+                size_of::<DrawElementsIndirectCommand>() as _,
+            );
+        }
+    }
+
     /// Wrapper of `glGetError(...)`
     #[inline]
     pub fn check() -> Result<(), String> {
@@ -298,3 +441,13 @@ impl Context {
         }
     }
 }
+
+/// Wrapper of `glGetString(...)`, returning an empty string if `name` is not available.
+fn get_string(name: gl::types::GLenum) -> String {
+    let ptr = unsafe { gl::GetString(name) };
+    if ptr.is_null() {
+        return String::new();
+    }
+    let c_str = unsafe { std::ffi::CStr::from_ptr(ptr as _) };
+    c_str.to_string_lossy().into_owned()
+}
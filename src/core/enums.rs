@@ -71,6 +71,14 @@ pub enum Target {
     Uniform,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IndexedTarget {
+    AtomicCounter,
+    ShaderStorage,
+    TransformFeedback,
+    Uniform,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Usage {
     StaticDraw,
@@ -92,6 +100,21 @@ impl Mask {
     pub const STENCIL_BUFFER_BIT: u32 = gl::STENCIL_BUFFER_BIT;
 }
 
+pub struct BufferFlag;
+
+impl BufferFlag {
+    pub const DYNAMIC_STORAGE_BIT: u32 = gl::DYNAMIC_STORAGE_BIT;
+    pub const CLIENT_STORAGE_BIT: u32 = gl::CLIENT_STORAGE_BIT;
+    pub const MAP_READ_BIT: u32 = gl::MAP_READ_BIT;
+    pub const MAP_WRITE_BIT: u32 = gl::MAP_WRITE_BIT;
+    pub const MAP_PERSISTENT_BIT: u32 = gl::MAP_PERSISTENT_BIT;
+    pub const MAP_COHERENT_BIT: u32 = gl::MAP_COHERENT_BIT;
+    pub const MAP_INVALIDATE_RANGE_BIT: u32 = gl::MAP_INVALIDATE_RANGE_BIT;
+    pub const MAP_INVALIDATE_BUFFER_BIT: u32 = gl::MAP_INVALIDATE_BUFFER_BIT;
+    pub const MAP_FLUSH_EXPLICIT_BIT: u32 = gl::MAP_FLUSH_EXPLICIT_BIT;
+    pub const MAP_UNSYNCHRONIZED_BIT: u32 = gl::MAP_UNSYNCHRONIZED_BIT;
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ShaderType {
     Vertex,
@@ -526,6 +549,18 @@ impl Target {
     }
 }
 
+impl IndexedTarget {
+    #[inline]
+    pub(super) const fn to_gl_target(self) -> GLenum {
+        match self {
+            IndexedTarget::AtomicCounter => gl::ATOMIC_COUNTER_BUFFER,
+            IndexedTarget::ShaderStorage => gl::SHADER_STORAGE_BUFFER,
+            IndexedTarget::TransformFeedback => gl::TRANSFORM_FEEDBACK_BUFFER,
+            IndexedTarget::Uniform => gl::UNIFORM_BUFFER,
+        }
+    }
+}
+
 impl Usage {
     #[inline]
     pub(super) const fn to_gl_usage(self) -> GLenum {
@@ -1115,11 +1150,18 @@ pub enum GlType {
     i32,
     f32,
     f64,
+    HalfFloat,
+    Fixed,
+    Int2_10_10_10Rev,
+    UnsignedInt2_10_10_10Rev,
+    UnsignedInt10F11F11FRev,
 }
 
 impl GlType {
+    /// Return the size of one component in bytes,
+    /// or of the whole packed value for packed types.
     #[inline]
-    pub(super) const fn size(self) -> usize {
+    pub(crate) const fn size(self) -> usize {
         match self {
             GlType::u8 => std::mem::size_of::<u8>(),
             GlType::i8 => std::mem::size_of::<i8>(),
@@ -1129,6 +1171,32 @@ impl GlType {
             GlType::i32 => std::mem::size_of::<i32>(),
             GlType::f32 => std::mem::size_of::<f32>(),
             GlType::f64 => std::mem::size_of::<f64>(),
+            GlType::HalfFloat => std::mem::size_of::<u16>(),
+            GlType::Fixed => std::mem::size_of::<i32>(),
+            GlType::Int2_10_10_10Rev
+            | GlType::UnsignedInt2_10_10_10Rev
+            | GlType::UnsignedInt10F11F11FRev => std::mem::size_of::<u32>(),
+        }
+    }
+
+    /// Return `true` if all components are packed into one 32-bit value.
+    #[inline]
+    pub const fn is_packed(self) -> bool {
+        matches!(
+            self,
+            GlType::Int2_10_10_10Rev
+                | GlType::UnsignedInt2_10_10_10Rev
+                | GlType::UnsignedInt10F11F11FRev
+        )
+    }
+
+    /// Return the size of an attribute of `count` components in bytes.
+    #[inline]
+    pub const fn attrib_size(self, count: usize) -> usize {
+        if self.is_packed() {
+            self.size()
+        } else {
+            self.size() * count
         }
     }
 
@@ -1143,10 +1211,28 @@ impl GlType {
             GlType::i32 => gl::INT,
             GlType::f32 => gl::FLOAT,
             GlType::f64 => gl::DOUBLE,
+            GlType::HalfFloat => gl::HALF_FLOAT,
+            GlType::Fixed => gl::FIXED,
+            GlType::Int2_10_10_10Rev => gl::INT_2_10_10_10_REV,
+            GlType::UnsignedInt2_10_10_10Rev => gl::UNSIGNED_INT_2_10_10_10_REV,
+            GlType::UnsignedInt10F11F11FRev => gl::UNSIGNED_INT_10F_11F_11F_REV,
         }
     }
 }
 
+/// How the data of a vertex attribute reaches the shader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AttribKind {
+    /// Converted to float, for `float`/`vec` inputs.
+    Float,
+    /// Integer data normalized to `[0, 1]` or `[-1, 1]`, for `float`/`vec` inputs.
+    Normalized,
+    /// Kept as integer, for `int`/`uint`/`ivec`/`uvec` inputs.
+    Integer,
+    /// Kept as 64-bit float, for `double`/`dvec` inputs.
+    Double,
+}
+
 pub struct TexCubeMap {
     index: u8,
 }
@@ -1189,3 +1275,385 @@ impl Filter {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SyncStatus {
+    AlreadySignaled,
+    ConditionSatisfied,
+    TimeoutExpired,
+    WaitFailed,
+}
+
+impl SyncStatus {
+    #[inline]
+    pub(super) const fn from_gl_enum(value: GLenum) -> Self {
+        match value {
+            gl::ALREADY_SIGNALED => SyncStatus::AlreadySignaled,
+            gl::CONDITION_SATISFIED => SyncStatus::ConditionSatisfied,
+            gl::TIMEOUT_EXPIRED => SyncStatus::TimeoutExpired,
+            _ => SyncStatus::WaitFailed,
+        }
+    }
+
+    /// Return `true` if the fence has been signaled.
+    #[inline]
+    pub const fn is_signaled(self) -> bool {
+        matches!(
+            self,
+            SyncStatus::AlreadySignaled | SyncStatus::ConditionSatisfied
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GlslScalar {
+    Float,
+    Double,
+    Int,
+    UInt,
+    Bool,
+}
+
+/// Type of a variable declared in GLSL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GlslType {
+    Float,
+    Vec2,
+    Vec3,
+    Vec4,
+    Double,
+    DVec2,
+    DVec3,
+    DVec4,
+    Int,
+    IVec2,
+    IVec3,
+    IVec4,
+    UInt,
+    UVec2,
+    UVec3,
+    UVec4,
+    Bool,
+    BVec2,
+    BVec3,
+    BVec4,
+    Mat2,
+    Mat3,
+    Mat4,
+    Mat2x3,
+    Mat2x4,
+    Mat3x2,
+    Mat3x4,
+    Mat4x2,
+    Mat4x3,
+    DMat2,
+    DMat3,
+    DMat4,
+    DMat2x3,
+    DMat2x4,
+    DMat3x2,
+    DMat3x4,
+    DMat4x2,
+    DMat4x3,
+    Sampler1D,
+    Sampler2D,
+    Sampler3D,
+    SamplerCube,
+    Sampler1DShadow,
+    Sampler2DShadow,
+    Sampler1DArray,
+    Sampler2DArray,
+    SamplerCubeShadow,
+    Sampler2DArrayShadow,
+    SamplerCubeArray,
+    Sampler2DMultisample,
+    SamplerBuffer,
+    ISampler2D,
+    ISampler3D,
+    USampler2D,
+    USampler3D,
+    Image2D,
+    Image3D,
+    IImage2D,
+    UImage2D,
+    AtomicUInt,
+    /// Any other type, e.g. less common samplers and images.
+    Other(GLenum),
+}
+
+impl GlslType {
+    #[inline]
+    pub(crate) const fn from_gl_enum(value: GLenum) -> Self {
+        match value {
+            gl::FLOAT => GlslType::Float,
+            gl::FLOAT_VEC2 => GlslType::Vec2,
+            gl::FLOAT_VEC3 => GlslType::Vec3,
+            gl::FLOAT_VEC4 => GlslType::Vec4,
+            gl::DOUBLE => GlslType::Double,
+            gl::DOUBLE_VEC2 => GlslType::DVec2,
+            gl::DOUBLE_VEC3 => GlslType::DVec3,
+            gl::DOUBLE_VEC4 => GlslType::DVec4,
+            gl::INT => GlslType::Int,
+            gl::INT_VEC2 => GlslType::IVec2,
+            gl::INT_VEC3 => GlslType::IVec3,
+            gl::INT_VEC4 => GlslType::IVec4,
+            gl::UNSIGNED_INT => GlslType::UInt,
+            gl::UNSIGNED_INT_VEC2 => GlslType::UVec2,
+            gl::UNSIGNED_INT_VEC3 => GlslType::UVec3,
+            gl::UNSIGNED_INT_VEC4 => GlslType::UVec4,
+            gl::BOOL => GlslType::Bool,
+            gl::BOOL_VEC2 => GlslType::BVec2,
+            gl::BOOL_VEC3 => GlslType::BVec3,
+            gl::BOOL_VEC4 => GlslType::BVec4,
+            gl::FLOAT_MAT2 => GlslType::Mat2,
+            gl::FLOAT_MAT3 => GlslType::Mat3,
+            gl::FLOAT_MAT4 => GlslType::Mat4,
+            gl::FLOAT_MAT2x3 => GlslType::Mat2x3,
+            gl::FLOAT_MAT2x4 => GlslType::Mat2x4,
+            gl::FLOAT_MAT3x2 => GlslType::Mat3x2,
+            gl::FLOAT_MAT3x4 => GlslType::Mat3x4,
+            gl::FLOAT_MAT4x2 => GlslType::Mat4x2,
+            gl::FLOAT_MAT4x3 => GlslType::Mat4x3,
+            gl::DOUBLE_MAT2 => GlslType::DMat2,
+            gl::DOUBLE_MAT3 => GlslType::DMat3,
+            gl::DOUBLE_MAT4 => GlslType::DMat4,
+            gl::DOUBLE_MAT2x3 => GlslType::DMat2x3,
+            gl::DOUBLE_MAT2x4 => GlslType::DMat2x4,
+            gl::DOUBLE_MAT3x2 => GlslType::DMat3x2,
+            gl::DOUBLE_MAT3x4 => GlslType::DMat3x4,
+            gl::DOUBLE_MAT4x2 => GlslType::DMat4x2,
+            gl::DOUBLE_MAT4x3 => GlslType::DMat4x3,
+            gl::SAMPLER_1D => GlslType::Sampler1D,
+            gl::SAMPLER_2D => GlslType::Sampler2D,
+            gl::SAMPLER_3D => GlslType::Sampler3D,
+            gl::SAMPLER_CUBE => GlslType::SamplerCube,
+            gl::SAMPLER_1D_SHADOW => GlslType::Sampler1DShadow,
+            gl::SAMPLER_2D_SHADOW => GlslType::Sampler2DShadow,
+            gl::SAMPLER_1D_ARRAY => GlslType::Sampler1DArray,
+            gl::SAMPLER_2D_ARRAY => GlslType::Sampler2DArray,
+            gl::SAMPLER_CUBE_SHADOW => GlslType::SamplerCubeShadow,
+            gl::SAMPLER_2D_ARRAY_SHADOW => GlslType::Sampler2DArrayShadow,
+            gl::SAMPLER_CUBE_MAP_ARRAY => GlslType::SamplerCubeArray,
+            gl::SAMPLER_2D_MULTISAMPLE => GlslType::Sampler2DMultisample,
+            gl::SAMPLER_BUFFER => GlslType::SamplerBuffer,
+            gl::INT_SAMPLER_2D => GlslType::ISampler2D,
+            gl::INT_SAMPLER_3D => GlslType::ISampler3D,
+            gl::UNSIGNED_INT_SAMPLER_2D => GlslType::USampler2D,
+            gl::UNSIGNED_INT_SAMPLER_3D => GlslType::USampler3D,
+            gl::IMAGE_2D => GlslType::Image2D,
+            gl::IMAGE_3D => GlslType::Image3D,
+            gl::INT_IMAGE_2D => GlslType::IImage2D,
+            gl::UNSIGNED_INT_IMAGE_2D => GlslType::UImage2D,
+            gl::UNSIGNED_INT_ATOMIC_COUNTER => GlslType::AtomicUInt,
+            _ => GlslType::Other(value),
+        }
+    }
+
+    /// Return the scalar type of the components, `None` for opaque types like samplers.
+    #[inline]
+    pub const fn scalar(self) -> Option<GlslScalar> {
+        match self {
+            GlslType::Float
+            | GlslType::Vec2
+            | GlslType::Vec3
+            | GlslType::Vec4
+            | GlslType::Mat2
+            | GlslType::Mat3
+            | GlslType::Mat4
+            | GlslType::Mat2x3
+            | GlslType::Mat2x4
+            | GlslType::Mat3x2
+            | GlslType::Mat3x4
+            | GlslType::Mat4x2
+            | GlslType::Mat4x3 => Some(GlslScalar::Float),
+            GlslType::Double
+            | GlslType::DVec2
+            | GlslType::DVec3
+            | GlslType::DVec4
+            | GlslType::DMat2
+            | GlslType::DMat3
+            | GlslType::DMat4
+            | GlslType::DMat2x3
+            | GlslType::DMat2x4
+            | GlslType::DMat3x2
+            | GlslType::DMat3x4
+            | GlslType::DMat4x2
+            | GlslType::DMat4x3 => Some(GlslScalar::Double),
+            GlslType::Int | GlslType::IVec2 | GlslType::IVec3 | GlslType::IVec4 => {
+                Some(GlslScalar::Int)
+            }
+            GlslType::UInt | GlslType::UVec2 | GlslType::UVec3 | GlslType::UVec4 => {
+                Some(GlslScalar::UInt)
+            }
+            GlslType::Bool | GlslType::BVec2 | GlslType::BVec3 | GlslType::BVec4 => {
+                Some(GlslScalar::Bool)
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the number of components of a vector, or of each column of a matrix.
+    #[inline]
+    pub const fn components(self) -> usize {
+        match self {
+            GlslType::Vec2
+            | GlslType::DVec2
+            | GlslType::IVec2
+            | GlslType::UVec2
+            | GlslType::BVec2
+            | GlslType::Mat2
+            | GlslType::Mat3x2
+            | GlslType::Mat4x2
+            | GlslType::DMat2
+            | GlslType::DMat3x2
+            | GlslType::DMat4x2 => 2,
+            GlslType::Vec3
+            | GlslType::DVec3
+            | GlslType::IVec3
+            | GlslType::UVec3
+            | GlslType::BVec3
+            | GlslType::Mat3
+            | GlslType::Mat2x3
+            | GlslType::Mat4x3
+            | GlslType::DMat3
+            | GlslType::DMat2x3
+            | GlslType::DMat4x3 => 3,
+            GlslType::Vec4
+            | GlslType::DVec4
+            | GlslType::IVec4
+            | GlslType::UVec4
+            | GlslType::BVec4
+            | GlslType::Mat4
+            | GlslType::Mat2x4
+            | GlslType::Mat3x4
+            | GlslType::DMat4
+            | GlslType::DMat2x4
+            | GlslType::DMat3x4 => 4,
+            _ => 1,
+        }
+    }
+
+    /// Return the number of locations taken by an input of this type.
+    ///
+    /// Each column takes one location, or two for `dvec3` and `dvec4` columns.
+    #[inline]
+    pub const fn locations(self) -> usize {
+        let double = matches!(self.scalar(), Some(GlslScalar::Double));
+        if double && self.components() > 2 {
+            self.columns() * 2
+        } else {
+            self.columns()
+        }
+    }
+
+    /// Return whether this is a sampler or an image type,
+    /// which is set to a texture unit or image unit as an `int`.
+    #[inline]
+    pub const fn is_opaque(self) -> bool {
+        match self {
+            GlslType::Sampler1D
+            | GlslType::Sampler2D
+            | GlslType::Sampler3D
+            | GlslType::SamplerCube
+            | GlslType::Sampler1DShadow
+            | GlslType::Sampler2DShadow
+            | GlslType::Sampler1DArray
+            | GlslType::Sampler2DArray
+            | GlslType::SamplerCubeShadow
+            | GlslType::Sampler2DArrayShadow
+            | GlslType::SamplerCubeArray
+            | GlslType::Sampler2DMultisample
+            | GlslType::SamplerBuffer
+            | GlslType::ISampler2D
+            | GlslType::ISampler3D
+            | GlslType::USampler2D
+            | GlslType::USampler3D
+            | GlslType::Image2D
+            | GlslType::Image3D
+            | GlslType::IImage2D
+            | GlslType::UImage2D => true,
+            GlslType::Other(value) => matches!(
+                value,
+                gl::SAMPLER_1D_ARRAY_SHADOW
+                    | gl::SAMPLER_2D_MULTISAMPLE_ARRAY
+                    | gl::SAMPLER_2D_RECT
+                    | gl::SAMPLER_2D_RECT_SHADOW
+                    | gl::SAMPLER_CUBE_MAP_ARRAY_SHADOW
+                    | gl::INT_SAMPLER_1D
+                    | gl::INT_SAMPLER_CUBE
+                    | gl::INT_SAMPLER_1D_ARRAY
+                    | gl::INT_SAMPLER_2D_ARRAY
+                    | gl::INT_SAMPLER_CUBE_MAP_ARRAY
+                    | gl::INT_SAMPLER_2D_MULTISAMPLE
+                    | gl::INT_SAMPLER_2D_MULTISAMPLE_ARRAY
+                    | gl::INT_SAMPLER_BUFFER
+                    | gl::INT_SAMPLER_2D_RECT
+                    | gl::UNSIGNED_INT_SAMPLER_1D
+                    | gl::UNSIGNED_INT_SAMPLER_CUBE
+                    | gl::UNSIGNED_INT_SAMPLER_1D_ARRAY
+                    | gl::UNSIGNED_INT_SAMPLER_2D_ARRAY
+                    | gl::UNSIGNED_INT_SAMPLER_CUBE_MAP_ARRAY
+                    | gl::UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE
+                    | gl::UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE_ARRAY
+                    | gl::UNSIGNED_INT_SAMPLER_BUFFER
+                    | gl::UNSIGNED_INT_SAMPLER_2D_RECT
+                    | gl::IMAGE_1D
+                    | gl::IMAGE_2D_RECT
+                    | gl::IMAGE_CUBE
+                    | gl::IMAGE_BUFFER
+                    | gl::IMAGE_1D_ARRAY
+                    | gl::IMAGE_2D_ARRAY
+                    | gl::IMAGE_CUBE_MAP_ARRAY
+                    | gl::IMAGE_2D_MULTISAMPLE
+                    | gl::IMAGE_2D_MULTISAMPLE_ARRAY
+                    | gl::INT_IMAGE_1D
+                    | gl::INT_IMAGE_3D
+                    | gl::INT_IMAGE_2D_RECT
+                    | gl::INT_IMAGE_CUBE
+                    | gl::INT_IMAGE_BUFFER
+                    | gl::INT_IMAGE_1D_ARRAY
+                    | gl::INT_IMAGE_2D_ARRAY
+                    | gl::INT_IMAGE_CUBE_MAP_ARRAY
+                    | gl::INT_IMAGE_2D_MULTISAMPLE
+                    | gl::INT_IMAGE_2D_MULTISAMPLE_ARRAY
+                    | gl::UNSIGNED_INT_IMAGE_1D
+                    | gl::UNSIGNED_INT_IMAGE_3D
+                    | gl::UNSIGNED_INT_IMAGE_2D_RECT
+                    | gl::UNSIGNED_INT_IMAGE_CUBE
+                    | gl::UNSIGNED_INT_IMAGE_BUFFER
+                    | gl::UNSIGNED_INT_IMAGE_1D_ARRAY
+                    | gl::UNSIGNED_INT_IMAGE_2D_ARRAY
+                    | gl::UNSIGNED_INT_IMAGE_CUBE_MAP_ARRAY
+                    | gl::UNSIGNED_INT_IMAGE_2D_MULTISAMPLE
+                    | gl::UNSIGNED_INT_IMAGE_2D_MULTISAMPLE_ARRAY
+            ),
+            _ => false,
+        }
+    }
+
+    /// Return the number of columns of a matrix, 1 otherwise.
+    #[inline]
+    pub const fn columns(self) -> usize {
+        match self {
+            GlslType::Mat2
+            | GlslType::Mat2x3
+            | GlslType::Mat2x4
+            | GlslType::DMat2
+            | GlslType::DMat2x3
+            | GlslType::DMat2x4 => 2,
+            GlslType::Mat3
+            | GlslType::Mat3x2
+            | GlslType::Mat3x4
+            | GlslType::DMat3
+            | GlslType::DMat3x2
+            | GlslType::DMat3x4 => 3,
+            GlslType::Mat4
+            | GlslType::Mat4x2
+            | GlslType::Mat4x3
+            | GlslType::DMat4
+            | GlslType::DMat4x2
+            | GlslType::DMat4x3 => 4,
+            _ => 1,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use gl::types::GLsync;
+
+use super::SyncStatus;
+
+/// Fence Sync Object
+pub struct Fence {
+    sync: GLsync,
+}
+
+impl Fence {
+    #[inline]
+    pub(super) fn new() -> Self {
+        let sync = unsafe { gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0) };
+        Self { sync }
+    }
+}
+
+impl Drop for Fence {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteSync(self.sync);
+        }
+    }
+}
+
+impl Fence {
+    /// Wrapper of `glClientWaitSync(...)`
+    ///
+    /// Block the current thread until the fence is signaled or `timeout` nanoseconds passed.
+    /// Pending commands are flushed before waiting.
+    #[inline]
+    pub fn client_wait(&self, timeout: u64) -> SyncStatus {
+        let status = unsafe { gl::ClientWaitSync(self.sync, gl::SYNC_FLUSH_COMMANDS_BIT, timeout) };
+        SyncStatus::from_gl_enum(status)
+    }
+
+    /// Wrapper of `glWaitSync(...)`
+    ///
+    /// Make the server wait until the fence is signaled, without blocking the client.
+    #[inline]
+    pub fn wait(&self) {
+        unsafe {
+            gl::WaitSync(self.sync, 0, gl::TIMEOUT_IGNORED);
+        }
+    }
+
+    /// Return `true` if the fence has already been signaled.
+    #[inline]
+    pub fn is_signaled(&self) -> bool {
+        self.client_wait(0).is_signaled()
+    }
+}
@@ -1,6 +1,6 @@
 use gl::types::GLuint;
 
-use super::{Attachmect, Filter, FrameBufferTarget, RenderBuffer};
+use super::{Attachmect, Filter, FrameBufferTarget, GlObject, Objects, RenderBuffer};
 
 pub struct FrameBuffer {
     fbo: GLuint,
@@ -11,7 +11,7 @@ impl FrameBuffer {
     pub(super) fn new() -> Self {
         let mut fbo = 0;
         unsafe {
-            gl::GenFramebuffers(1, &mut fbo);
+            gl::CreateFramebuffers(1, &mut fbo);
         }
         Self { fbo }
     }
@@ -26,69 +26,31 @@ impl Drop for FrameBuffer {
     }
 }
 
-pub struct FrameBuffers {
-    fbos: Vec<FrameBuffer>,
-}
-
-impl FrameBuffers {
-    #[inline]
-    pub(super) fn new(count: usize) -> Self {
-        let mut fbos = Vec::with_capacity(count);
-        unsafe {
-            gl::GenFramebuffers(count as i32, fbos.as_mut_ptr());
-        }
-        Self {
-            fbos: fbos.into_iter().map(|fbo| FrameBuffer { fbo }).collect(),
-        }
-    }
+impl GlObject for FrameBuffer {
+    type Target = ();
 
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &FrameBuffer> {
-        self.fbos.iter()
+    unsafe fn create(_: (), ids: &mut [GLuint]) {
+        gl::CreateFramebuffers(ids.len() as _, ids.as_mut_ptr());
     }
 
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut FrameBuffer> {
-        self.fbos.iter_mut()
+    unsafe fn delete(ids: &[GLuint]) {
+        gl::DeleteFramebuffers(ids.len() as _, ids.as_ptr());
     }
-}
-
-pub struct IntoIter {
-    fbos: Vec<Option<FrameBuffer>>,
-    index: usize,
-}
 
-impl IntoIter {
     #[inline]
-    pub fn new(fbos: Vec<FrameBuffer>) -> Self {
-        Self {
-            fbos: fbos.into_iter().map(|fbo| Some(fbo)).collect(),
-            index: 0,
-        }
+    unsafe fn from_id(fbo: GLuint) -> Self {
+        Self { fbo }
     }
-}
-
-impl Iterator for IntoIter {
-    type Item = FrameBuffer;
 
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        let result = self.fbos.get_mut(self.index)?.take();
-        self.index += 1;
-        result
+    fn id(&self) -> GLuint {
+        self.fbo
     }
 }
 
-impl IntoIterator for FrameBuffers {
-    type Item = FrameBuffer;
-
-    type IntoIter = IntoIter;
-
-    #[inline]
-    fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter::new(self.fbos)
-    }
-}
+pub type FrameBuffers = Objects<FrameBuffer>;
 
 impl FrameBuffer {
     /// Wrapper of `glBindFramebuffer(...)`
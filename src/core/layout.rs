@@ -0,0 +1,277 @@
+use super::{AttribKind, GlType};
+
+/// Description of one vertex attribute stored in a buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VertexAttribute {
+    /// Name of the field the attribute comes from.
+    pub name: &'static str,
+    /// Location of the attribute in the shader.
+    pub location: u32,
+    /// Number of consecutive locations, more than 1 for matrices and 64-bit vectors of 3 or 4 components.
+    /// Each column reads `count` components following the previous one.
+    pub locations: u32,
+    /// Type of each component.
+    pub ty: GlType,
+    /// Number of components.
+    pub count: usize,
+    /// How the data is passed to the shader.
+    pub kind: AttribKind,
+    /// Offset of the attribute from the start of the vertex in bytes.
+    pub offset: usize,
+    /// The attribute advances once per `divisor` instances, or once per vertex if 0.
+    pub divisor: u32,
+}
+
+impl VertexAttribute {
+    /// Iterate the first location and byte offset of each column of this attribute.
+    #[inline]
+    pub fn slots(&self) -> impl Iterator<Item = (u32, usize)> {
+        let (location, offset) = (self.location, self.offset);
+        let size = self.ty.attrib_size(self.count);
+        let step = self.column_locations();
+        (0..self.locations / step).map(move |i| (location + i * step, offset + i as usize * size))
+    }
+
+    /// Return the number of locations taken by each column.
+    #[inline]
+    pub fn column_locations(&self) -> u32 {
+        column_locations(self.ty, self.count)
+    }
+}
+
+/// Return the number of locations taken by a column of `count` components of `ty`,
+/// 2 for `dvec3` and `dvec4`, 1 otherwise.
+#[inline]
+const fn column_locations(ty: GlType, count: usize) -> u32 {
+    if matches!(ty, GlType::f64) && count > 2 {
+        2
+    } else {
+        1
+    }
+}
+
+/// Types whose memory layout describes the vertex attributes of a buffer.
+///
+/// It is usually implemented by `#[derive(VertexLayout)]` on a `#[repr(C)]` struct.
+pub trait VertexLayout: Sized {
+    /// The attributes of one vertex.
+    const ATTRIBUTES: &'static [VertexAttribute];
+
+    /// The distance between two vertices in bytes.
+    const STRIDE: usize = size_of::<Self>();
+
+    /// Return the position of this vertex, e.g. to compute bounding boxes.
+    ///
+    /// It returns `None` by default, `#[derive(VertexLayout)]` returns the field marked by
+    /// `#[vertex(position)]`.
+    #[inline]
+    fn position(&self) -> Option<[f32; 3]> {
+        None
+    }
+}
+
+/// Types which can be the position of a vertex, see `VertexLayout::position()`.
+pub trait VertexPosition {
+    /// Return `x`, `y` and `z`, dropping `w` if there is one.
+    fn to_position(&self) -> [f32; 3];
+}
+
+impl VertexPosition for [f32; 3] {
+    #[inline]
+    fn to_position(&self) -> [f32; 3] {
+        *self
+    }
+}
+
+impl VertexPosition for [f32; 4] {
+    #[inline]
+    fn to_position(&self) -> [f32; 3] {
+        [self[0], self[1], self[2]]
+    }
+}
+
+impl VertexPosition for mats::Vec<f32, 3> {
+    #[inline]
+    fn to_position(&self) -> [f32; 3] {
+        self[0]
+    }
+}
+
+impl VertexPosition for mats::Vec<f32, 4> {
+    #[inline]
+    fn to_position(&self) -> [f32; 3] {
+        [self[0][0], self[0][1], self[0][2]]
+    }
+}
+
+/// Types which can be used as a field of a vertex struct.
+pub trait VertexAttrib {
+    /// Type of each component.
+    const TYPE: GlType;
+    /// Number of components.
+    const COUNT: usize;
+    /// How the data is passed to the shader unless specified otherwise.
+    const KIND: AttribKind;
+    /// Number of consecutive locations taken.
+    const LOCATIONS: u32 = 1;
+    /// Whether this is a row-major matrix, which arrives transposed in a GLSL matrix input.
+    ///
+    /// `#[derive(VertexLayout)]` rejects such fields unless marked by `#[vertex(transposed)]`.
+    const TRANSPOSED: bool = false;
+}
+
+macro_rules! impl_vertex_attrib {
+    ($($type:ty => ($gl_type:expr, $kind:expr));+ $(;)?) => {
+        $(
+            impl VertexAttrib for $type {
+                const TYPE: GlType = $gl_type;
+                const COUNT: usize = 1;
+                const KIND: AttribKind = $kind;
+            }
+
+            impl<const N: usize> VertexAttrib for [$type; N] {
+                const TYPE: GlType = $gl_type;
+                const COUNT: usize = N;
+                const KIND: AttribKind = $kind;
+                const LOCATIONS: u32 = column_locations($gl_type, N);
+            }
+
+            impl<const N: usize> VertexAttrib for mats::Vec<$type, N> {
+                const TYPE: GlType = $gl_type;
+                const COUNT: usize = N;
+                const KIND: AttribKind = $kind;
+                const LOCATIONS: u32 = column_locations($gl_type, N);
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "half")]
+impl_vertex_attrib! {
+    half::f16 => (GlType::HalfFloat, AttribKind::Float);
+}
+
+impl_vertex_attrib! {
+    Fixed => (GlType::Fixed, AttribKind::Float);
+    u8 => (GlType::u8, AttribKind::Integer);
+    i8 => (GlType::i8, AttribKind::Integer);
+    u16 => (GlType::u16, AttribKind::Integer);
+    i16 => (GlType::i16, AttribKind::Integer);
+    u32 => (GlType::u32, AttribKind::Integer);
+    i32 => (GlType::i32, AttribKind::Integer);
+    f32 => (GlType::f32, AttribKind::Float);
+    f64 => (GlType::f64, AttribKind::Double);
+}
+
+macro_rules! impl_vertex_attrib_matrix {
+    ($($type:ty => ($gl_type:expr, $kind:expr));+ $(;)?) => {
+        $(
+            impl<const M: usize, const N: usize> VertexAttrib for [[$type; N]; M] {
+                const TYPE: GlType = $gl_type;
+                const COUNT: usize = N;
+                const KIND: AttribKind = $kind;
+                const LOCATIONS: u32 = M as u32 * column_locations($gl_type, N);
+            }
+
+            impl_vertex_attrib_matrix!(@mat $type, $gl_type, $kind, 2, 3, 4);
+        )+
+    };
+    // Each location reads a row, so only square matrices keep their GLSL type once transposed.
+    (@mat $type:ty, $gl_type:expr, $kind:expr, $($size:literal),+) => {
+        $(
+            impl VertexAttrib for mats::Mat<$type, $size, $size> {
+                const TYPE: GlType = $gl_type;
+                const COUNT: usize = $size;
+                const KIND: AttribKind = $kind;
+                const LOCATIONS: u32 = $size * column_locations($gl_type, $size);
+                const TRANSPOSED: bool = true;
+            }
+        )+
+    };
+}
+
+impl_vertex_attrib_matrix! {
+    f32 => (GlType::f32, AttribKind::Float);
+    f64 => (GlType::f64, AttribKind::Double);
+}
+
+/// 16.16 fixed point number used as vertex data.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed(pub i32);
+
+impl Fixed {
+    /// Convert `value` to the nearest fixed point number.
+    #[inline]
+    pub fn from_f32(value: f32) -> Self {
+        Self((value * 65536.0).round() as i32)
+    }
+
+    /// Convert this number to float.
+    #[inline]
+    pub fn to_f32(self) -> f32 {
+        self.0 as f32 / 65536.0
+    }
+}
+
+/// Four signed components packed as 2, 10, 10 and 10 bits from the high bits.
+///
+/// It is passed to the shader as 4 normalized components unless specified otherwise.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Int2_10_10_10Rev(pub u32);
+
+impl Int2_10_10_10Rev {
+    /// Pack the normalized components `[x, y, z, w]`, which are clamped to `[-1, 1]`.
+    #[inline]
+    pub fn from_normalized([x, y, z, w]: [f32; 4]) -> Self {
+        let pack = |value: f32, max: f32, bits: u32| {
+            ((value.clamp(-1.0, 1.0) * max).round() as i32 as u32) & ((1 << bits) - 1)
+        };
+        Self(
+            pack(x, 511.0, 10)
+                | pack(y, 511.0, 10) << 10
+                | pack(z, 511.0, 10) << 20
+                | pack(w, 1.0, 2) << 30,
+        )
+    }
+}
+
+/// Four unsigned components packed as 2, 10, 10 and 10 bits from the high bits.
+///
+/// It is passed to the shader as 4 normalized components unless specified otherwise.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct UnsignedInt2_10_10_10Rev(pub u32);
+
+impl UnsignedInt2_10_10_10Rev {
+    /// Pack the normalized components `[x, y, z, w]`, which are clamped to `[0, 1]`.
+    #[inline]
+    pub fn from_normalized([x, y, z, w]: [f32; 4]) -> Self {
+        let pack = |value: f32, max: f32| (value.clamp(0.0, 1.0) * max).round() as u32;
+        Self(pack(x, 1023.0) | pack(y, 1023.0) << 10 | pack(z, 1023.0) << 20 | pack(w, 3.0) << 30)
+    }
+}
+
+/// Three unsigned floats packed as 10, 11 and 11 bits from the high bits.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct UnsignedInt10F11F11FRev(pub u32);
+
+macro_rules! impl_vertex_attrib_packed {
+    ($($type:ty => ($gl_type:expr, $count:expr, $kind:expr));+ $(;)?) => {
+        $(
+            impl VertexAttrib for $type {
+                const TYPE: GlType = $gl_type;
+                const COUNT: usize = $count;
+                const KIND: AttribKind = $kind;
+            }
+        )+
+    };
+}
+
+impl_vertex_attrib_packed! {
+    Int2_10_10_10Rev => (GlType::Int2_10_10_10Rev, 4, AttribKind::Normalized);
+    UnsignedInt2_10_10_10Rev => (GlType::UnsignedInt2_10_10_10Rev, 4, AttribKind::Normalized);
+    UnsignedInt10F11F11FRev => (GlType::UnsignedInt10F11F11FRev, 3, AttribKind::Float);
+}
@@ -8,21 +8,36 @@ mod array;
 mod buffer;
 mod context;
 mod enums;
+mod fence;
 mod frame_buffer;
+mod layout;
+mod object;
 mod program;
+mod reflection;
 mod render_buffer;
 mod shader;
+mod std_layout;
 mod texture;
+mod uniform;
 
 pub use array::{Array, Arrays};
 pub use buffer::{Buffer, Buffers};
-pub use context::Context;
+pub use context::{Context, DrawElementsIndirectCommand};
 pub use enums::*;
+pub use fence::Fence;
 pub use frame_buffer::{FrameBuffer, FrameBuffers};
-pub use program::Program;
+pub use layout::{
+    Fixed, Int2_10_10_10Rev, UnsignedInt10F11F11FRev, UnsignedInt2_10_10_10Rev, VertexAttrib,
+    VertexAttribute, VertexLayout, VertexPosition,
+};
+pub use object::{GlObject, Objects};
+pub use program::{Program, ProgramVariable};
+pub use reflection::{BlockMember, InterfaceBlock, ProgramReflection};
 pub use render_buffer::{RenderBuffer, RenderBuffers};
 pub use shader::Shader;
+pub use std_layout::{Std140, Std430};
 pub use texture::{Texture, Textures};
+pub use uniform::{Uniform, UniformValue};
 
 trait GlTypeT {
     fn to_gl_type() -> gl::types::GLenum;
@@ -51,3 +66,40 @@ impl_gl_type! {
     f32 => gl::FLOAT;
     f64 => gl::DOUBLE;
 }
+
+#[cfg(feature = "half")]
+impl_gl_type! {
+    half::f16 => gl::HALF_FLOAT;
+}
+
+trait GlClearT: GlTypeT {
+    fn to_gl_clear_format() -> (gl::types::GLenum, gl::types::GLenum);
+}
+
+macro_rules! impl_gl_clear {
+    ($($type:ty => ($internal_format:expr, $format:expr));+ $(;)?) => {
+        $(
+            impl GlClearT for $type {
+                #[inline]
+                fn to_gl_clear_format() -> (gl::types::GLenum, gl::types::GLenum) {
+                    ($internal_format, $format)
+                }
+            }
+        )+
+    };
+}
+
+impl_gl_clear! {
+    u8 => (gl::R8UI, gl::RED_INTEGER);
+    i8 => (gl::R8I, gl::RED_INTEGER);
+    u16 => (gl::R16UI, gl::RED_INTEGER);
+    i16 => (gl::R16I, gl::RED_INTEGER);
+    u32 => (gl::R32UI, gl::RED_INTEGER);
+    i32 => (gl::R32I, gl::RED_INTEGER);
+    f32 => (gl::R32F, gl::RED);
+}
+
+#[cfg(feature = "half")]
+impl_gl_clear! {
+    half::f16 => (gl::R16F, gl::RED);
+}
@@ -0,0 +1,173 @@
+use std::{
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut, Index, IndexMut},
+    slice::SliceIndex,
+};
+
+use gl::types::GLuint;
+
+/// OpenGL object which can be created and deleted in batches.
+pub trait GlObject: Sized {
+    /// What the objects are created for, e.g. the target of textures, `()` for most objects.
+    type Target: Copy;
+
+    /// Create `ids.len()` objects for `target` and write their names to `ids`.
+    ///
+    /// # Safety
+    /// The objects are not owned by anyone, each name must be turned into
+    /// exactly one value by `from_id(...)` or deleted by `delete(...)`.
+    unsafe fn create(target: Self::Target, ids: &mut [GLuint]);
+
+    /// Delete all objects named by `ids`.
+    ///
+    /// # Safety
+    /// The names must not be used by any live value afterwards.
+    unsafe fn delete(ids: &[GLuint]);
+
+    /// Take ownership of the object named by `id`.
+    ///
+    /// # Safety
+    /// `id` must be a name created by `create(...)` that is not owned by any other value.
+    unsafe fn from_id(id: GLuint) -> Self;
+
+    /// Return the name of this object.
+    fn id(&self) -> GLuint;
+}
+
+/// Collection of OpenGL objects created by one call and deleted by one call.
+pub struct Objects<T: GlObject> {
+    objects: ManuallyDrop<Vec<T>>,
+}
+
+impl<T: GlObject<Target = ()>> Objects<T> {
+    /// Create `count` objects by one call.
+    ///
+    /// Objects of this crate are usually created by `Context`, e.g. `Context::new_buffers(...)`.
+    ///
+    /// # Panics
+    /// If `count` is 0.
+    #[inline]
+    pub fn new(count: usize) -> Self {
+        Self::with_target((), count)
+    }
+}
+
+impl<T: GlObject> Objects<T> {
+    /// Create `count` objects for `target` by one call.
+    ///
+    /// # Panics
+    /// If `count` is 0.
+    pub fn with_target(target: T::Target, count: usize) -> Self {
+        assert!(count > 0, "The number of objects must be greater than 0");
+        let mut ids = vec![0; count];
+        let objects = unsafe {
+            T::create(target, &mut ids);
+            ids.into_iter().map(|id| T::from_id(id)).collect()
+        };
+        Self {
+            objects: ManuallyDrop::new(objects),
+        }
+    }
+
+    /// Return an iterator of the objects.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.objects.iter()
+    }
+
+    /// Return an iterator of the objects.
+    #[inline]
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.objects.iter_mut()
+    }
+
+    /// Return the number of objects.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Return the names of the objects.
+    #[inline]
+    pub fn ids(&self) -> Vec<GLuint> {
+        self.objects.iter().map(T::id).collect()
+    }
+}
+
+impl<T: GlObject> Drop for Objects<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let ids = self.ids();
+        unsafe {
+            T::delete(&ids);
+            // The objects have been deleted above, so their own `Drop` must not run.
+            self.objects.set_len(0);
+            ManuallyDrop::drop(&mut self.objects);
+        }
+    }
+}
+
+impl<T: GlObject> Deref for Objects<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.objects
+    }
+}
+
+impl<T: GlObject> DerefMut for Objects<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.objects
+    }
+}
+
+impl<T: GlObject, I: SliceIndex<[T]>> Index<I> for Objects<T> {
+    type Output = I::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        &self.objects[index]
+    }
+}
+
+impl<T: GlObject, I: SliceIndex<[T]>> IndexMut<I> for Objects<T> {
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.objects[index]
+    }
+}
+
+impl<T: GlObject> IntoIterator for Objects<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Each object yielded by the iterator is deleted on its own when dropped.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let mut this = ManuallyDrop::new(self);
+        let objects = unsafe { ManuallyDrop::take(&mut this.objects) };
+        objects.into_iter()
+    }
+}
+
+impl<'a, T: GlObject> IntoIterator for &'a Objects<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: GlObject> IntoIterator for &'a mut Objects<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -1,19 +1,27 @@
-use std::ptr;
+use std::{cell::RefCell, collections::HashMap, ptr};
 
-use gl::{types::GLuint, INFO_LOG_LENGTH};
+use gl::{
+    types::{GLenum, GLint, GLuint},
+    INFO_LOG_LENGTH,
+};
 
-use super::Shader;
+use super::{GlslType, Shader, Uniform, UniformValue};
 
 /// Shader Program Object
 pub struct Program {
-    program: GLuint,
+    pub(super) program: GLuint,
+    /// Uniform locations by name, cleared whenever the program is linked.
+    locations: RefCell<HashMap<String, GLint>>,
 }
 
 impl Program {
     #[inline]
     pub(super) fn new() -> Self {
         let program = unsafe { gl::CreateProgram() };
-        Self { program }
+        Self {
+            program,
+            locations: RefCell::default(),
+        }
     }
 }
 
@@ -43,12 +51,21 @@ impl Program {
         }
     }
 
+    /// Detach a shader from this program, which lets it be deleted once dropped.
+    #[inline]
+    pub fn detach(&self, shader: &Shader) {
+        unsafe {
+            gl::DetachShader(self.program, shader.shader);
+        }
+    }
+
     /// Link this program.
     ///
     /// It will return `Ok(())` if the program is linked successfully,
     /// otherwise it will return `Err(String)` which contains the error message.
     #[inline]
     pub fn link(&self) -> Result<(), String> {
+        self.locations.borrow_mut().clear();
         unsafe {
             gl::LinkProgram(self.program);
         }
@@ -70,7 +87,9 @@ impl Program {
                     msg.as_mut_ptr() as _,
                 );
             }
-            let err = unsafe { String::from_utf8_unchecked(msg) };
+            // The log ends with a null terminator and is not guaranteed to be UTF-8.
+            let end = msg.iter().position(|&byte| byte == 0).unwrap_or(msg.len());
+            let err = String::from_utf8_lossy(&msg[..end]).into_owned();
             return Err(err);
         }
         Ok(())
@@ -82,14 +101,30 @@ impl Program {
     /// you can use `unsafe { self.link_unchecked() }` to improve performance.
     #[inline]
     pub unsafe fn link_unchecked(&self) {
+        self.locations.borrow_mut().clear();
         gl::LinkProgram(self.program);
     }
 }
 
 use mats::uniform::SetUniform;
 impl Program {
+    /// Set the uniform `name` of this program, which must be in use.
+    ///
+    /// The location is looked up once and cached until the program is linked again.
     #[inline]
     pub fn set_uniform<T: SetUniform>(&self, name: &str, value: &T) -> Result<(), String> {
+        let location = self.uniform_location(name)?;
+        value.give(location);
+        Ok(())
+    }
+
+    /// Return the location of the uniform `name`, cached until the program is linked again.
+    ///
+    /// Wrapper of `glGetUniformLocation(...)`
+    pub fn uniform_location(&self, name: &str) -> Result<i32, String> {
+        if let Some(&location) = self.locations.borrow().get(name) {
+            return Ok(location);
+        }
         let name_ptr = match std::ffi::CString::new(name) {
             Ok(c_str) => c_str,
             Err(_) => return Err(format!("Invalid uniform name '{}'", name)),
@@ -98,7 +133,283 @@ impl Program {
         if location == -1 {
             return Err(format!("Uniform '{}' not found", name));
         }
-        value.give(location);
+        self.locations
+            .borrow_mut()
+            .insert(name.to_owned(), location);
+        Ok(location)
+    }
+
+    /// Return a typed handle of the uniform `name`,
+    /// checking once that its GLSL type can be set from `T`.
+    ///
+    /// Elements of arrays are named like `lights[2]`.
+    pub fn uniform<T: UniformValue>(&self, name: &str) -> Result<Uniform<T>, String> {
+        let location = self.uniform_location(name)?;
+        let ty = self.uniform_type(name)?;
+        if !T::accepts(ty) {
+            return Err(format!(
+                "Uniform '{}' is a {:?}, which cannot be set from {}",
+                name,
+                ty,
+                std::any::type_name::<T>()
+            ));
+        }
+        Ok(Uniform::new(self.program, location, ty))
+    }
+
+    /// Return the GLSL type of the uniform `name`, which may be an array element.
+    fn uniform_type(&self, name: &str) -> Result<GlslType, String> {
+        // Arrays are only reported by their first element, which has the same type.
+        let base = match name
+            .strip_suffix(']')
+            .and_then(|name| name.rsplit_once('['))
+        {
+            Some((base, _)) => base,
+            None => name,
+        };
+        let name_ptr = match std::ffi::CString::new(base) {
+            Ok(c_str) => c_str,
+            Err(_) => return Err(format!("Invalid uniform name '{}'", name)),
+        };
+        let index =
+            unsafe { gl::GetProgramResourceIndex(self.program, gl::UNIFORM, name_ptr.as_ptr()) };
+        if index == gl::INVALID_INDEX {
+            return Err(format!("Uniform '{}' not found", name));
+        }
+        let [ty] = self.resource_properties(gl::UNIFORM, index, [gl::TYPE]);
+        Ok(GlslType::from_gl_enum(ty as _))
+    }
+}
+
+impl Program {
+    /// Connect the uniform block `name` to the uniform buffer binding point `binding`.
+    ///
+    /// Wrapper of `glUniformBlockBinding(...)`
+    #[inline]
+    pub fn uniform_block_binding(&self, name: &str, binding: u32) -> Result<(), String> {
+        let name_ptr = match std::ffi::CString::new(name) {
+            Ok(c_str) => c_str,
+            Err(_) => return Err(format!("Invalid uniform block name '{}'", name)),
+        };
+        let index = unsafe { gl::GetUniformBlockIndex(self.program, name_ptr.as_ptr()) };
+        if index == gl::INVALID_INDEX {
+            return Err(format!("Uniform block '{}' not found", name));
+        }
+        unsafe {
+            gl::UniformBlockBinding(self.program, index, binding);
+        }
+        Ok(())
+    }
+
+    /// Connect the shader storage block `name` to the shader storage buffer binding point `binding`.
+    ///
+    /// Wrapper of `glShaderStorageBlockBinding(...)`
+    #[inline]
+    pub fn shader_storage_block_binding(&self, name: &str, binding: u32) -> Result<(), String> {
+        let name_ptr = match std::ffi::CString::new(name) {
+            Ok(c_str) => c_str,
+            Err(_) => return Err(format!("Invalid shader storage block name '{}'", name)),
+        };
+        let index = unsafe {
+            gl::GetProgramResourceIndex(self.program, gl::SHADER_STORAGE_BLOCK, name_ptr.as_ptr())
+        };
+        if index == gl::INVALID_INDEX {
+            return Err(format!("Shader storage block '{}' not found", name));
+        }
+        unsafe {
+            gl::ShaderStorageBlockBinding(self.program, index, binding);
+        }
+        Ok(())
+    }
+}
+
+/// Active variable of a program interface, e.g. a vertex input or a uniform.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProgramVariable {
+    pub name: String,
+    pub ty: GlslType,
+    /// Number of array elements, 1 for variables which are not arrays.
+    pub array_size: usize,
+    /// Location of the variable, -1 if it has none, e.g. built-in variables.
+    pub location: i32,
+}
+
+impl Program {
+    /// Return the active vertex inputs of this program, excluding built-in variables.
+    ///
+    /// Wrapper of `glGetProgramResource*(GL_PROGRAM_INPUT, ...)`
+    #[inline]
+    pub fn active_inputs(&self) -> Vec<ProgramVariable> {
+        self.variables(gl::PROGRAM_INPUT)
+    }
+
+    /// Return the active variables of `interface` which have a type and a location.
+    pub(super) fn variables(&self, interface: GLenum) -> Vec<ProgramVariable> {
+        let properties = [gl::TYPE, gl::ARRAY_SIZE, gl::LOCATION];
+        (0..self.resource_count(interface))
+            .filter_map(|index| {
+                let name = self.resource_name(interface, index);
+                if name.starts_with("gl_") {
+                    return None;
+                }
+                let [ty, array_size, location] =
+                    self.resource_properties(interface, index, properties);
+                Some(ProgramVariable {
+                    name,
+                    ty: GlslType::from_gl_enum(ty as _),
+                    array_size: array_size.max(1) as _,
+                    location,
+                })
+            })
+            .collect()
+    }
+
+    /// Wrapper of `glGetProgramInterfaceiv(..., GL_ACTIVE_RESOURCES, ...)`
+    pub(super) fn resource_count(&self, interface: GLenum) -> u32 {
+        let mut count = 0;
+        unsafe {
+            gl::GetProgramInterfaceiv(self.program, interface, gl::ACTIVE_RESOURCES, &mut count);
+        }
+        count.max(0) as _
+    }
+
+    /// Wrapper of `glGetProgramResourceName(...)`
+    pub(super) fn resource_name(&self, interface: GLenum, index: u32) -> String {
+        let [length] = self.resource_properties(interface, index, [gl::NAME_LENGTH]);
+        let mut name = vec![0u8; length.max(1) as usize];
+        let mut written = 0;
+        unsafe {
+            gl::GetProgramResourceName(
+                self.program,
+                interface,
+                index,
+                name.len() as _,
+                &mut written,
+                name.as_mut_ptr() as _,
+            );
+        }
+        name.truncate(written.max(0) as usize);
+        String::from_utf8_lossy(&name).into_owned()
+    }
+
+    /// Wrapper of `glGetProgramResourceiv(...)`
+    pub(super) fn resource_properties<const N: usize>(
+        &self,
+        interface: GLenum,
+        index: u32,
+        properties: [GLenum; N],
+    ) -> [GLint; N] {
+        let mut values = [0; N];
+        unsafe {
+            gl::GetProgramResourceiv(
+                self.program,
+                interface,
+                index,
+                N as _,
+                properties.as_ptr(),
+                N as _,
+                ptr::null_mut(),
+                values.as_mut_ptr(),
+            );
+        }
+        values
+    }
+
+    /// Bind the vertex input `name` to `location`, which takes effect on the next link.
+    ///
+    /// Wrapper of `glBindAttribLocation(...)`
+    #[inline]
+    pub fn bind_attrib_location(&self, location: u32, name: &str) -> Result<(), String> {
+        let name_ptr = match std::ffi::CString::new(name) {
+            Ok(c_str) => c_str,
+            Err(_) => return Err(format!("Invalid attribute name '{}'", name)),
+        };
+        unsafe {
+            gl::BindAttribLocation(self.program, location, name_ptr.as_ptr());
+        }
+        Ok(())
+    }
+
+    /// Bind the fragment output `name` to the color number `location`,
+    /// which takes effect on the next link.
+    ///
+    /// Wrapper of `glBindFragDataLocation(...)`
+    #[inline]
+    pub fn bind_frag_data_location(&self, location: u32, name: &str) -> Result<(), String> {
+        let name_ptr = match std::ffi::CString::new(name) {
+            Ok(c_str) => c_str,
+            Err(_) => return Err(format!("Invalid fragment output name '{}'", name)),
+        };
+        unsafe {
+            gl::BindFragDataLocation(self.program, location, name_ptr.as_ptr());
+        }
+        Ok(())
+    }
+}
+
+impl Program {
+    /// Allow the binary of this program to be retrieved, which takes effect on the next link.
+    ///
+    /// Wrapper of `glProgramParameteri(GL_PROGRAM_BINARY_RETRIEVABLE_HINT, ...)`
+    #[inline]
+    pub fn binary_retrievable_hint(&self) {
+        unsafe {
+            gl::ProgramParameteri(
+                self.program,
+                gl::PROGRAM_BINARY_RETRIEVABLE_HINT,
+                gl::TRUE as _,
+            );
+        }
+    }
+
+    /// Return the format and data of the binary of this linked program,
+    /// `None` if the driver does not provide one.
+    ///
+    /// Wrapper of `glGetProgramBinary(...)`
+    pub fn binary(&self) -> Option<(u32, Vec<u8>)> {
+        let mut length = 0;
+        unsafe {
+            gl::GetProgramiv(self.program, gl::PROGRAM_BINARY_LENGTH, &mut length);
+        }
+        if length <= 0 {
+            return None;
+        }
+        let mut data = vec![0u8; length as usize];
+        let (mut written, mut format) = (0, 0);
+        unsafe {
+            gl::GetProgramBinary(
+                self.program,
+                length,
+                &mut written,
+                &mut format,
+                data.as_mut_ptr() as _,
+            );
+        }
+        data.truncate(written.max(0) as usize);
+        (!data.is_empty()).then_some((format, data))
+    }
+
+    /// Load a binary previously returned by `binary()` instead of linking shaders.
+    ///
+    /// It will return `Err(String)` if the driver rejects the binary,
+    /// e.g. because it was created by another driver version.
+    ///
+    /// Wrapper of `glProgramBinary(...)`
+    pub fn load_binary(&self, format: u32, data: &[u8]) -> Result<(), String> {
+        self.locations.borrow_mut().clear();
+        unsafe {
+            gl::ProgramBinary(self.program, format, data.as_ptr() as _, data.len() as _);
+        }
+        let mut sign = 0;
+        unsafe {
+            gl::GetProgramiv(self.program, gl::LINK_STATUS, &mut sign);
+        }
+        if sign == 0 {
+            return Err(format!(
+                "The driver rejected the program binary of format {:#x}",
+                format
+            ));
+        }
         Ok(())
     }
 }
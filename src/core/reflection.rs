@@ -0,0 +1,199 @@
+use gl::types::{GLenum, GLint};
+
+use super::{GlslType, Program, ProgramVariable};
+
+/// Member of a uniform block or shader storage block.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockMember {
+    pub name: String,
+    pub ty: GlslType,
+    /// Number of array elements, 1 for members which are not arrays.
+    pub array_size: usize,
+    /// Byte offset from the start of the block.
+    pub offset: usize,
+    /// Bytes between array elements, 0 for members which are not arrays.
+    pub array_stride: usize,
+    /// Bytes between columns, or rows if `row_major`, 0 for members which are not matrices.
+    pub matrix_stride: usize,
+    pub row_major: bool,
+}
+
+/// Active uniform block or shader storage block of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InterfaceBlock {
+    pub name: String,
+    /// Index of the block, as used by `glUniformBlockBinding(...)`.
+    pub index: u32,
+    /// Buffer binding point the block reads from.
+    pub binding: u32,
+    /// Minimum size in bytes of a buffer backing the block.
+    pub size: usize,
+    pub members: Vec<BlockMember>,
+}
+
+impl InterfaceBlock {
+    /// Return the member called `name`, the `[0]` of arrays may be left out.
+    #[inline]
+    pub fn member(&self, name: &str) -> Option<&BlockMember> {
+        self.members
+            .iter()
+            .find(|member| matches_name(&member.name, name))
+    }
+}
+
+/// Everything a linked program exposes, as returned by `Program::reflect()`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ProgramReflection {
+    /// Uniforms of the default block, i.e. outside of any uniform block.
+    pub uniforms: Vec<ProgramVariable>,
+    /// Vertex inputs, excluding built-in variables.
+    pub inputs: Vec<ProgramVariable>,
+    /// Fragment outputs, excluding built-in variables.
+    pub outputs: Vec<ProgramVariable>,
+    pub uniform_blocks: Vec<InterfaceBlock>,
+    pub storage_blocks: Vec<InterfaceBlock>,
+}
+
+impl ProgramReflection {
+    /// Return the uniform called `name`, the `[0]` of arrays may be left out.
+    #[inline]
+    pub fn uniform(&self, name: &str) -> Option<&ProgramVariable> {
+        self.uniforms
+            .iter()
+            .find(|uniform| matches_name(&uniform.name, name))
+    }
+
+    /// Return the vertex input called `name`.
+    #[inline]
+    pub fn input(&self, name: &str) -> Option<&ProgramVariable> {
+        self.inputs
+            .iter()
+            .find(|input| matches_name(&input.name, name))
+    }
+
+    /// Return the fragment output called `name`.
+    #[inline]
+    pub fn output(&self, name: &str) -> Option<&ProgramVariable> {
+        self.outputs
+            .iter()
+            .find(|output| matches_name(&output.name, name))
+    }
+
+    /// Return the uniform block called `name`.
+    #[inline]
+    pub fn uniform_block(&self, name: &str) -> Option<&InterfaceBlock> {
+        self.uniform_blocks.iter().find(|block| block.name == name)
+    }
+
+    /// Return the shader storage block called `name`.
+    #[inline]
+    pub fn storage_block(&self, name: &str) -> Option<&InterfaceBlock> {
+        self.storage_blocks.iter().find(|block| block.name == name)
+    }
+}
+
+impl Program {
+    /// Query the active uniforms, inputs, outputs and blocks of this linked program.
+    ///
+    /// Wrapper of `glGetProgramInterfaceiv(...)` and `glGetProgramResource*(...)`
+    pub fn reflect(&self) -> ProgramReflection {
+        ProgramReflection {
+            uniforms: self.default_block_uniforms(),
+            inputs: self.variables(gl::PROGRAM_INPUT),
+            outputs: self.variables(gl::PROGRAM_OUTPUT),
+            uniform_blocks: self.blocks(gl::UNIFORM_BLOCK, gl::UNIFORM),
+            storage_blocks: self.blocks(gl::SHADER_STORAGE_BLOCK, gl::BUFFER_VARIABLE),
+        }
+    }
+
+    fn default_block_uniforms(&self) -> Vec<ProgramVariable> {
+        let properties = [gl::TYPE, gl::ARRAY_SIZE, gl::LOCATION, gl::BLOCK_INDEX];
+        (0..self.resource_count(gl::UNIFORM))
+            .filter_map(|index| {
+                let [ty, array_size, location, block] =
+                    self.resource_properties(gl::UNIFORM, index, properties);
+                if block != -1 {
+                    return None;
+                }
+                Some(ProgramVariable {
+                    name: self.resource_name(gl::UNIFORM, index),
+                    ty: GlslType::from_gl_enum(ty as _),
+                    array_size: array_size.max(1) as _,
+                    location,
+                })
+            })
+            .collect()
+    }
+
+    /// Return the blocks of `interface` whose members belong to `member_interface`.
+    fn blocks(&self, interface: GLenum, member_interface: GLenum) -> Vec<InterfaceBlock> {
+        let properties = [
+            gl::BUFFER_BINDING,
+            gl::BUFFER_DATA_SIZE,
+            gl::NUM_ACTIVE_VARIABLES,
+        ];
+        (0..self.resource_count(interface))
+            .map(|index| {
+                let [binding, size, count] = self.resource_properties(interface, index, properties);
+                let members = self
+                    .active_variables(interface, index, count)
+                    .into_iter()
+                    .map(|member| self.block_member(member_interface, member as _))
+                    .collect();
+                InterfaceBlock {
+                    name: self.resource_name(interface, index),
+                    index,
+                    binding: binding as _,
+                    size: size as _,
+                    members,
+                }
+            })
+            .collect()
+    }
+
+    fn block_member(&self, interface: GLenum, index: u32) -> BlockMember {
+        let properties = [
+            gl::TYPE,
+            gl::ARRAY_SIZE,
+            gl::OFFSET,
+            gl::ARRAY_STRIDE,
+            gl::MATRIX_STRIDE,
+            gl::IS_ROW_MAJOR,
+        ];
+        let [ty, array_size, offset, array_stride, matrix_stride, row_major] =
+            self.resource_properties(interface, index, properties);
+        BlockMember {
+            name: self.resource_name(interface, index),
+            ty: GlslType::from_gl_enum(ty as _),
+            array_size: array_size.max(1) as _,
+            offset: offset as _,
+            array_stride: array_stride.max(0) as _,
+            matrix_stride: matrix_stride.max(0) as _,
+            row_major: row_major != 0,
+        }
+    }
+
+    /// Wrapper of `glGetProgramResourceiv(..., GL_ACTIVE_VARIABLES, ...)`
+    fn active_variables(&self, interface: GLenum, index: u32, count: GLint) -> Vec<GLint> {
+        let mut variables = vec![0; count.max(0) as usize];
+        unsafe {
+            gl::GetProgramResourceiv(
+                self.program,
+                interface,
+                index,
+                1,
+                &gl::ACTIVE_VARIABLES,
+                variables.len() as _,
+                std::ptr::null_mut(),
+                variables.as_mut_ptr(),
+            );
+        }
+        variables
+    }
+}
+
+/// Compare names as reported by GL, where arrays end with `[0]`.
+#[inline]
+fn matches_name(reported: &str, name: &str) -> bool {
+    reported == name || reported.strip_suffix("[0]") == Some(name)
+}
@@ -1,6 +1,6 @@
 use gl::types::GLuint;
 
-use super::RenderBufferFormat;
+use super::{GlObject, Objects, RenderBufferFormat};
 
 pub struct RenderBuffer {
     pub(super) rbo: GLuint,
@@ -11,7 +11,7 @@ impl RenderBuffer {
     pub(super) fn new() -> Self {
         let mut rbo = 0;
         unsafe {
-            gl::GenRenderbuffers(1, &mut rbo);
+            gl::CreateRenderbuffers(1, &mut rbo);
         }
         Self { rbo }
     }
@@ -26,68 +26,31 @@ impl Drop for RenderBuffer {
     }
 }
 
-pub struct RenderBuffers {
-    rbos: Vec<RenderBuffer>,
-}
+impl GlObject for RenderBuffer {
+    type Target = ();
 
-impl RenderBuffers {
     #[inline]
-    pub(super) fn new(count: usize) -> Self {
-        let mut rbos = Vec::with_capacity(count);
-        unsafe {
-            gl::GenRenderbuffers(count as i32, rbos.as_mut_ptr());
-        }
-        Self {
-            rbos: rbos.into_iter().map(|rbo| RenderBuffer { rbo }).collect(),
-        }
+    unsafe fn create(_: (), ids: &mut [GLuint]) {
+        gl::CreateRenderbuffers(ids.len() as _, ids.as_mut_ptr());
     }
 
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &RenderBuffer> {
-        self.rbos.iter()
+    unsafe fn delete(ids: &[GLuint]) {
+        gl::DeleteRenderbuffers(ids.len() as _, ids.as_ptr());
     }
 
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut RenderBuffer> {
-        self.rbos.iter_mut()
-    }
-}
-
-pub struct IntoIter {
-    rbos: Vec<Option<RenderBuffer>>,
-    index: usize,
-}
-
-impl IntoIter {
-    #[inline]
-    fn new(rbos: Vec<RenderBuffer>) -> Self {
-        Self {
-            rbos: rbos.into_iter().map(|rbo| Some(rbo)).collect(),
-            index: 0,
-        }
+    unsafe fn from_id(rbo: GLuint) -> Self {
+        Self { rbo }
     }
-}
-
-impl Iterator for IntoIter {
-    type Item = RenderBuffer;
 
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        let result = self.rbos.get_mut(self.index)?.take();
-        self.index += 1;
-        result
+    fn id(&self) -> GLuint {
+        self.rbo
     }
 }
 
-impl IntoIterator for RenderBuffers {
-    type Item = RenderBuffer;
-    type IntoIter = IntoIter;
-
-    #[inline]
-    fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter::new(self.rbos)
-    }
-}
+pub type RenderBuffers = Objects<RenderBuffer>;
 
 impl RenderBuffer {
     /// Wrapper for `glBindRenderBuffer(...)`
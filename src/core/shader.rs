@@ -65,7 +65,9 @@ impl Shader {
                     msg.as_mut_ptr() as _,
                 );
             }
-            let err = unsafe { String::from_utf8_unchecked(msg) };
+            // The log ends with a null terminator and is not guaranteed to be UTF-8.
+            let end = msg.iter().position(|&byte| byte == 0).unwrap_or(msg.len());
+            let err = String::from_utf8_lossy(&msg[..end]).into_owned();
             return Err(err);
         }
         Ok(())
@@ -0,0 +1,212 @@
+use mats::Mat;
+
+/// Types which can be written to a uniform or storage block with the `std140` layout.
+///
+/// It is usually implemented by `#[derive(Std140)]` on a struct.
+/// Vectors and matrices are `mats` types, e.g. `Vec3<f32>` is a `vec3` and
+/// the row-major `Mat<f32, R, C>` is a `matCxR`, while `[T; N]` is a GLSL array.
+pub trait Std140 {
+    /// Base alignment in bytes.
+    const ALIGN: usize;
+    /// Size in bytes, including the padding at the end of arrays and structs.
+    const SIZE: usize;
+
+    /// Write this value to the start of `bytes`, leaving the padding untouched.
+    fn write_std140(&self, bytes: &mut [u8]);
+
+    /// Return the bytes of this value, with zeroed padding.
+    #[inline]
+    fn to_std140(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::SIZE];
+        self.write_std140(&mut bytes);
+        bytes
+    }
+}
+
+/// Types which can be written to a storage block with the `std430` layout.
+///
+/// It is usually implemented by `#[derive(Std430)]` on a struct.
+/// Unlike `std140`, arrays and structs are not aligned to 16 bytes.
+pub trait Std430 {
+    /// Base alignment in bytes.
+    const ALIGN: usize;
+    /// Size in bytes, including the padding at the end of arrays and structs.
+    const SIZE: usize;
+
+    /// Write this value to the start of `bytes`, leaving the padding untouched.
+    fn write_std430(&self, bytes: &mut [u8]);
+
+    /// Return the bytes of this value, with zeroed padding.
+    #[inline]
+    fn to_std430(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::SIZE];
+        self.write_std430(&mut bytes);
+        bytes
+    }
+}
+
+macro_rules! impl_scalar {
+    ($($type:ty => |$value:ident| $bytes:expr);+ $(;)?) => {
+        $(
+            impl Std140 for $type {
+                const ALIGN: usize = size_of::<$type>();
+                const SIZE: usize = size_of::<$type>();
+
+                #[inline]
+                fn write_std140(&self, bytes: &mut [u8]) {
+                    let $value = *self;
+                    bytes[..size_of::<$type>()].copy_from_slice(&$bytes);
+                }
+            }
+
+            impl Std430 for $type {
+                const ALIGN: usize = size_of::<$type>();
+                const SIZE: usize = size_of::<$type>();
+
+                #[inline]
+                fn write_std430(&self, bytes: &mut [u8]) {
+                    self.write_std140(bytes);
+                }
+            }
+        )+
+    };
+}
+
+// GLSL `bool` takes 4 bytes in a block.
+impl_scalar! {
+    f32 => |value| value.to_ne_bytes();
+    f64 => |value| value.to_ne_bytes();
+    i32 => |value| value.to_ne_bytes();
+    u32 => |value| value.to_ne_bytes();
+}
+
+impl Std140 for bool {
+    const ALIGN: usize = 4;
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn write_std140(&self, bytes: &mut [u8]) {
+        (*self as u32).write_std140(bytes);
+    }
+}
+
+impl Std430 for bool {
+    const ALIGN: usize = 4;
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn write_std430(&self, bytes: &mut [u8]) {
+        (*self as u32).write_std430(bytes);
+    }
+}
+
+/// A `vecN` is aligned to 2 components if `N` is 2, otherwise to 4 components.
+const fn vector_align(scalar: usize, count: usize) -> usize {
+    if count == 2 {
+        scalar * 2
+    } else {
+        scalar * 4
+    }
+}
+
+macro_rules! impl_vector {
+    ($($type:ty),+ ; $count:literal) => {
+        $(
+            impl Std140 for Mat<$type, 1, $count> {
+                const ALIGN: usize = vector_align(<$type as Std140>::SIZE, $count);
+                const SIZE: usize = <$type as Std140>::SIZE * $count;
+
+                #[inline]
+                fn write_std140(&self, bytes: &mut [u8]) {
+                    for (i, value) in self[0].iter().enumerate() {
+                        value.write_std140(&mut bytes[i * <$type as Std140>::SIZE..]);
+                    }
+                }
+            }
+
+            impl Std430 for Mat<$type, 1, $count> {
+                const ALIGN: usize = <Self as Std140>::ALIGN;
+                const SIZE: usize = <Self as Std140>::SIZE;
+
+                #[inline]
+                fn write_std430(&self, bytes: &mut [u8]) {
+                    self.write_std140(bytes);
+                }
+            }
+        )+
+    };
+}
+
+impl_vector!(f32, f64, i32, u32, bool; 2);
+impl_vector!(f32, f64, i32, u32, bool; 3);
+impl_vector!(f32, f64, i32, u32, bool; 4);
+
+/// Write the columns of a row-major matrix, each column starting `stride` bytes after the last.
+#[inline]
+fn write_columns<T: Std140, const R: usize, const C: usize>(
+    matrix: &Mat<T, R, C>,
+    stride: usize,
+    bytes: &mut [u8],
+) {
+    for column in 0..C {
+        for row in 0..R {
+            matrix[row][column].write_std140(&mut bytes[column * stride + row * T::SIZE..]);
+        }
+    }
+}
+
+macro_rules! impl_matrix {
+    ($type:ty; $($rows:literal x $columns:literal),+) => {
+        $(
+            // A `matCxR` is stored like an array of `C` column vectors.
+            impl Std140 for Mat<$type, $rows, $columns> {
+                const ALIGN: usize = vector_align(size_of::<$type>(), $rows).next_multiple_of(16);
+                const SIZE: usize = <Self as Std140>::ALIGN * $columns;
+
+                #[inline]
+                fn write_std140(&self, bytes: &mut [u8]) {
+                    write_columns(self, <Self as Std140>::ALIGN, bytes);
+                }
+            }
+
+            impl Std430 for Mat<$type, $rows, $columns> {
+                const ALIGN: usize = vector_align(size_of::<$type>(), $rows);
+                const SIZE: usize = <Self as Std430>::ALIGN * $columns;
+
+                #[inline]
+                fn write_std430(&self, bytes: &mut [u8]) {
+                    write_columns(self, <Self as Std430>::ALIGN, bytes);
+                }
+            }
+        )+
+    };
+}
+
+impl_matrix!(f32; 2 x 2, 2 x 3, 2 x 4, 3 x 2, 3 x 3, 3 x 4, 4 x 2, 4 x 3, 4 x 4);
+impl_matrix!(f64; 2 x 2, 2 x 3, 2 x 4, 3 x 2, 3 x 3, 3 x 4, 4 x 2, 4 x 3, 4 x 4);
+
+impl<T: Std140, const N: usize> Std140 for [T; N] {
+    const ALIGN: usize = T::ALIGN.next_multiple_of(16);
+    const SIZE: usize = T::SIZE.next_multiple_of(<Self as Std140>::ALIGN) * N;
+
+    #[inline]
+    fn write_std140(&self, bytes: &mut [u8]) {
+        let stride = T::SIZE.next_multiple_of(<Self as Std140>::ALIGN);
+        for (i, value) in self.iter().enumerate() {
+            value.write_std140(&mut bytes[i * stride..]);
+        }
+    }
+}
+
+impl<T: Std430, const N: usize> Std430 for [T; N] {
+    const ALIGN: usize = T::ALIGN;
+    const SIZE: usize = T::SIZE.next_multiple_of(T::ALIGN) * N;
+
+    #[inline]
+    fn write_std430(&self, bytes: &mut [u8]) {
+        let stride = T::SIZE.next_multiple_of(T::ALIGN);
+        for (i, value) in self.iter().enumerate() {
+            value.write_std430(&mut bytes[i * stride..]);
+        }
+    }
+}
@@ -1,10 +1,8 @@
-use std::ops::{Index, IndexMut};
-
 use gl::types::GLuint;
 
 use super::{
-    FrameBufferAttachment, FrameBufferTarget, ImageFormat, ImageTarget, InternalFormat,
-    PixelDataType, TexParam, TexParamPair, TexTarget, TextureTarget,
+    FrameBufferAttachment, FrameBufferTarget, GlObject, ImageFormat, ImageTarget, InternalFormat,
+    Objects, PixelDataType, TexParam, TexParamPair, TexTarget, TextureTarget,
 };
 
 /// Texture object
@@ -14,9 +12,9 @@ pub struct Texture {
 
 impl Texture {
     #[inline]
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(target: TexTarget) -> Self {
         let mut id = 0;
-        unsafe { gl::GenTextures(1, &mut id) };
+        unsafe { gl::CreateTextures(target.to_gl_target(), 1, &mut id) };
         Self { id }
     }
 }
@@ -28,64 +26,33 @@ impl Drop for Texture {
     }
 }
 
-pub struct Textures {
-    textures: Vec<Texture>,
-}
+impl GlObject for Texture {
+    /// A texture can only ever be bound to the target it is created for.
+    type Target = TexTarget;
 
-/// Texture objects
-impl Textures {
     #[inline]
-    pub(super) fn new(count: usize) -> Self {
-        let mut id = Vec::with_capacity(count);
-        unsafe {
-            gl::GenTextures(count as _, id.as_mut_ptr());
-        }
-        Self {
-            textures: id.into_iter().map(|id| Texture { id }).collect(),
-        }
+    unsafe fn create(target: TexTarget, ids: &mut [GLuint]) {
+        gl::CreateTextures(target.to_gl_target(), ids.len() as _, ids.as_mut_ptr());
     }
 
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &Texture> {
-        self.textures.iter()
+    unsafe fn delete(ids: &[GLuint]) {
+        gl::DeleteTextures(ids.len() as _, ids.as_ptr());
     }
 
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Texture> {
-        self.textures.iter_mut()
-    }
-
-    #[inline]
-    pub fn count(&self) -> usize {
-        self.textures.len()
-    }
-}
-
-impl IntoIterator for Textures {
-    type Item = Texture;
-    type IntoIter = std::vec::IntoIter<Texture>;
-
-    #[inline]
-    fn into_iter(self) -> Self::IntoIter {
-        self.textures.into_iter()
+    unsafe fn from_id(id: GLuint) -> Self {
+        Self { id }
     }
-}
-
-impl Index<usize> for Textures {
-    type Output = Texture;
 
     #[inline]
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.textures[index]
+    fn id(&self) -> GLuint {
+        self.id
     }
 }
 
-impl IndexMut<usize> for Textures {
-    #[inline]
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.textures[index]
-    }
-}
+/// Texture objects
+pub type Textures = Objects<Texture>;
 
 impl Texture {
     /// Wrapper of `glBindTexture(...)`
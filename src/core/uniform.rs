@@ -0,0 +1,181 @@
+use std::marker::PhantomData;
+
+use gl::types::{GLint, GLuint};
+use mats::Mat;
+
+use super::GlslType;
+
+/// Value which can be given to a uniform with `glProgramUniform*(...)`.
+pub trait UniformValue {
+    /// Return whether a uniform declared as `ty` can be set from this type.
+    fn accepts(ty: GlslType) -> bool;
+
+    /// Set the uniform at `location` of `program` to this value.
+    ///
+    /// # Safety
+    /// `program` must be a linked program and `location` must belong to
+    /// a uniform of a type accepted by `Self::accepts(...)`.
+    unsafe fn program_uniform(&self, program: GLuint, location: GLint);
+}
+
+macro_rules! impl_uniform_value {
+    ($($type:ty => |$ty:ident| $accepts:expr, |$p:ident, $l:ident, $v:ident| $set:expr;)+) => {
+        $(
+            impl UniformValue for $type {
+                #[inline]
+                fn accepts($ty: GlslType) -> bool {
+                    $accepts
+                }
+
+                #[inline]
+                unsafe fn program_uniform(&self, $p: GLuint, $l: GLint) {
+                    let $v = self;
+                    $set
+                }
+            }
+        )+
+    };
+}
+
+impl_uniform_value! {
+    f32 => |ty| ty == GlslType::Float, |p, l, v| gl::ProgramUniform1f(p, l, *v);
+    f64 => |ty| ty == GlslType::Double, |p, l, v| gl::ProgramUniform1d(p, l, *v);
+    i32 => |ty| matches!(ty, GlslType::Int | GlslType::Bool) || ty.is_opaque(),
+        |p, l, v| gl::ProgramUniform1i(p, l, *v);
+    u32 => |ty| matches!(ty, GlslType::UInt | GlslType::Bool),
+        |p, l, v| gl::ProgramUniform1ui(p, l, *v);
+    bool => |ty| ty == GlslType::Bool, |p, l, v| gl::ProgramUniform1i(p, l, *v as _);
+    [f32; 2] => |ty| ty == GlslType::Vec2, |p, l, v| gl::ProgramUniform2fv(p, l, 1, v.as_ptr());
+    [f32; 3] => |ty| ty == GlslType::Vec3, |p, l, v| gl::ProgramUniform3fv(p, l, 1, v.as_ptr());
+    [f32; 4] => |ty| ty == GlslType::Vec4, |p, l, v| gl::ProgramUniform4fv(p, l, 1, v.as_ptr());
+    [f64; 2] => |ty| ty == GlslType::DVec2, |p, l, v| gl::ProgramUniform2dv(p, l, 1, v.as_ptr());
+    [f64; 3] => |ty| ty == GlslType::DVec3, |p, l, v| gl::ProgramUniform3dv(p, l, 1, v.as_ptr());
+    [f64; 4] => |ty| ty == GlslType::DVec4, |p, l, v| gl::ProgramUniform4dv(p, l, 1, v.as_ptr());
+    [i32; 2] => |ty| matches!(ty, GlslType::IVec2 | GlslType::BVec2),
+        |p, l, v| gl::ProgramUniform2iv(p, l, 1, v.as_ptr());
+    [i32; 3] => |ty| matches!(ty, GlslType::IVec3 | GlslType::BVec3),
+        |p, l, v| gl::ProgramUniform3iv(p, l, 1, v.as_ptr());
+    [i32; 4] => |ty| matches!(ty, GlslType::IVec4 | GlslType::BVec4),
+        |p, l, v| gl::ProgramUniform4iv(p, l, 1, v.as_ptr());
+    [u32; 2] => |ty| matches!(ty, GlslType::UVec2 | GlslType::BVec2),
+        |p, l, v| gl::ProgramUniform2uiv(p, l, 1, v.as_ptr());
+    [u32; 3] => |ty| matches!(ty, GlslType::UVec3 | GlslType::BVec3),
+        |p, l, v| gl::ProgramUniform3uiv(p, l, 1, v.as_ptr());
+    [u32; 4] => |ty| matches!(ty, GlslType::UVec4 | GlslType::BVec4),
+        |p, l, v| gl::ProgramUniform4uiv(p, l, 1, v.as_ptr());
+    Mat<f32, 1, 2> => |ty| ty == GlslType::Vec2, |p, l, v| gl::ProgramUniform2fv(p, l, 1, v[0].as_ptr());
+    Mat<f32, 1, 3> => |ty| ty == GlslType::Vec3, |p, l, v| gl::ProgramUniform3fv(p, l, 1, v[0].as_ptr());
+    Mat<f32, 1, 4> => |ty| ty == GlslType::Vec4, |p, l, v| gl::ProgramUniform4fv(p, l, 1, v[0].as_ptr());
+    Mat<f64, 1, 2> => |ty| ty == GlslType::DVec2, |p, l, v| gl::ProgramUniform2dv(p, l, 1, v[0].as_ptr());
+    Mat<f64, 1, 3> => |ty| ty == GlslType::DVec3, |p, l, v| gl::ProgramUniform3dv(p, l, 1, v[0].as_ptr());
+    Mat<f64, 1, 4> => |ty| ty == GlslType::DVec4, |p, l, v| gl::ProgramUniform4dv(p, l, 1, v[0].as_ptr());
+    Mat<i32, 1, 2> => |ty| matches!(ty, GlslType::IVec2 | GlslType::BVec2),
+        |p, l, v| gl::ProgramUniform2iv(p, l, 1, v[0].as_ptr());
+    Mat<i32, 1, 3> => |ty| matches!(ty, GlslType::IVec3 | GlslType::BVec3),
+        |p, l, v| gl::ProgramUniform3iv(p, l, 1, v[0].as_ptr());
+    Mat<i32, 1, 4> => |ty| matches!(ty, GlslType::IVec4 | GlslType::BVec4),
+        |p, l, v| gl::ProgramUniform4iv(p, l, 1, v[0].as_ptr());
+    Mat<u32, 1, 2> => |ty| matches!(ty, GlslType::UVec2 | GlslType::BVec2),
+        |p, l, v| gl::ProgramUniform2uiv(p, l, 1, v[0].as_ptr());
+    Mat<u32, 1, 3> => |ty| matches!(ty, GlslType::UVec3 | GlslType::BVec3),
+        |p, l, v| gl::ProgramUniform3uiv(p, l, 1, v[0].as_ptr());
+    Mat<u32, 1, 4> => |ty| matches!(ty, GlslType::UVec4 | GlslType::BVec4),
+        |p, l, v| gl::ProgramUniform4uiv(p, l, 1, v[0].as_ptr());
+}
+
+// Matrices of Mats are row-major, `Mat<T, R, C>` is the GLSL `matCxR` and is transposed.
+impl_uniform_value! {
+    Mat<f32, 2, 2> => |ty| ty == GlslType::Mat2,
+        |p, l, v| gl::ProgramUniformMatrix2fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f32, 3, 3> => |ty| ty == GlslType::Mat3,
+        |p, l, v| gl::ProgramUniformMatrix3fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f32, 4, 4> => |ty| ty == GlslType::Mat4,
+        |p, l, v| gl::ProgramUniformMatrix4fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f32, 3, 2> => |ty| ty == GlslType::Mat2x3,
+        |p, l, v| gl::ProgramUniformMatrix2x3fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f32, 4, 2> => |ty| ty == GlslType::Mat2x4,
+        |p, l, v| gl::ProgramUniformMatrix2x4fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f32, 2, 3> => |ty| ty == GlslType::Mat3x2,
+        |p, l, v| gl::ProgramUniformMatrix3x2fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f32, 4, 3> => |ty| ty == GlslType::Mat3x4,
+        |p, l, v| gl::ProgramUniformMatrix3x4fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f32, 2, 4> => |ty| ty == GlslType::Mat4x2,
+        |p, l, v| gl::ProgramUniformMatrix4x2fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f32, 3, 4> => |ty| ty == GlslType::Mat4x3,
+        |p, l, v| gl::ProgramUniformMatrix4x3fv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 2, 2> => |ty| ty == GlslType::DMat2,
+        |p, l, v| gl::ProgramUniformMatrix2dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 3, 3> => |ty| ty == GlslType::DMat3,
+        |p, l, v| gl::ProgramUniformMatrix3dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 4, 4> => |ty| ty == GlslType::DMat4,
+        |p, l, v| gl::ProgramUniformMatrix4dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 3, 2> => |ty| ty == GlslType::DMat2x3,
+        |p, l, v| gl::ProgramUniformMatrix2x3dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 4, 2> => |ty| ty == GlslType::DMat2x4,
+        |p, l, v| gl::ProgramUniformMatrix2x4dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 2, 3> => |ty| ty == GlslType::DMat3x2,
+        |p, l, v| gl::ProgramUniformMatrix3x2dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 4, 3> => |ty| ty == GlslType::DMat3x4,
+        |p, l, v| gl::ProgramUniformMatrix3x4dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 2, 4> => |ty| ty == GlslType::DMat4x2,
+        |p, l, v| gl::ProgramUniformMatrix4x2dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+    Mat<f64, 3, 4> => |ty| ty == GlslType::DMat4x3,
+        |p, l, v| gl::ProgramUniformMatrix4x3dv(p, l, 1, gl::TRUE, v[0].as_ptr());
+}
+
+/// Typed handle of a uniform, as returned by `Program::uniform::<T>(...)`.
+///
+/// The type is checked once when the handle is created,
+/// setting a value is then a single `glProgramUniform*(...)` call
+/// which does not need the program to be in use.
+///
+/// The handle does not keep the program alive and is invalidated by relinking it.
+#[derive(Debug)]
+pub struct Uniform<T> {
+    program: GLuint,
+    location: GLint,
+    ty: GlslType,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T> Clone for Uniform<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Uniform<T> {}
+
+impl<T: UniformValue> Uniform<T> {
+    #[inline]
+    pub(super) fn new(program: GLuint, location: GLint, ty: GlslType) -> Self {
+        Self {
+            program,
+            location,
+            ty,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the uniform to `value`.
+    ///
+    /// Wrapper of `glProgramUniform*(...)`
+    #[inline]
+    pub fn set(&self, value: &T) {
+        unsafe {
+            value.program_uniform(self.program, self.location);
+        }
+    }
+
+    #[inline]
+    pub fn location(&self) -> i32 {
+        self.location
+    }
+
+    /// Return the type the uniform is declared with in GLSL.
+    #[inline]
+    pub fn ty(&self) -> GlslType {
+        self.ty
+    }
+}
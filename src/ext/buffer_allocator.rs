@@ -0,0 +1,342 @@
+use std::ops::Range;
+
+use crate::{Buffer, BufferFlag, Context};
+
+/// Range of bytes allocated from a `BufferAllocator` or a `RangeAllocator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BufferSlice {
+    /// Index of the buffer of a `BufferAllocator` holding the slice, 0 for a `RangeAllocator`.
+    pub buffer: usize,
+    pub offset: usize,
+    pub size: usize,
+}
+
+impl BufferSlice {
+    /// Return the byte range of this slice.
+    #[inline]
+    pub const fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.size
+    }
+
+    /// Return the index of the first element of this slice for elements of `stride` bytes.
+    ///
+    /// This is the value to pass as base vertex or first index of a draw call.
+    #[inline]
+    pub const fn first(&self, stride: usize) -> usize {
+        self.offset / stride
+    }
+
+    /// Return the number of elements of `stride` bytes in this slice.
+    #[inline]
+    pub const fn count(&self, stride: usize) -> usize {
+        self.size / stride
+    }
+}
+
+/// First-fit free-list allocator of byte ranges.
+///
+/// This type only does the bookkeeping and never touches OpenGL,
+/// `BufferAllocator` uses it to manage the storage of a buffer.
+#[derive(Debug, Clone)]
+pub struct RangeAllocator {
+    size: usize,
+    free: Vec<Range<usize>>,
+}
+
+impl RangeAllocator {
+    /// Create an allocator managing `size` bytes.
+    pub fn new(size: usize) -> Self {
+        let mut free = Vec::new();
+        if size > 0 {
+            free.push(0..size);
+        }
+        Self { size, free }
+    }
+
+    /// Allocate `size` bytes whose offset is a multiple of `alignment`.
+    ///
+    /// The alignment does not need to be a power of two, so the stride of a
+    /// vertex can be used to keep allocations addressable by base vertex.
+    /// An empty slice at offset 0 is returned for `size == 0`, which takes no space.
+    /// It will return `None` if no free range is large enough.
+    pub fn alloc(&mut self, size: usize, alignment: usize) -> Option<BufferSlice> {
+        assert!(alignment > 0, "The alignment must be greater than 0");
+        if size == 0 {
+            return Some(BufferSlice {
+                buffer: 0,
+                offset: 0,
+                size: 0,
+            });
+        }
+        let (index, start) = self.free.iter().enumerate().find_map(|(index, range)| {
+            let start = range.start.div_ceil(alignment) * alignment;
+            let end = start.checked_add(size)?;
+            (end <= range.end).then_some((index, start))
+        })?;
+
+        let range = self.free.remove(index);
+        let end = start + size;
+        if end < range.end {
+            self.free.insert(index, end..range.end);
+        }
+        if range.start < start {
+            self.free.insert(index, range.start..start);
+        }
+        Some(BufferSlice {
+            buffer: 0,
+            offset: start,
+            size,
+        })
+    }
+
+    /// Return `slice` to the allocator, merging it with adjacent free ranges.
+    ///
+    /// # Panics
+    /// If `slice` is out of range or overlaps a free range, e.g. when it is freed twice.
+    pub fn free(&mut self, slice: BufferSlice) {
+        assert!(
+            slice
+                .offset
+                .checked_add(slice.size)
+                .is_some_and(|end| end <= self.size),
+            "The slice is out of range of the allocator"
+        );
+        let range = slice.range();
+        if range.is_empty() {
+            return;
+        }
+        let index = self.free.partition_point(|free| free.start < range.start);
+        assert!(
+            index == 0 || self.free[index - 1].end <= range.start,
+            "The slice overlaps a free range"
+        );
+        assert!(
+            index == self.free.len() || range.end <= self.free[index].start,
+            "The slice overlaps a free range"
+        );
+
+        let merge_prev = index > 0 && self.free[index - 1].end == range.start;
+        let merge_next = index < self.free.len() && self.free[index].start == range.end;
+        match (merge_prev, merge_next) {
+            (true, true) => {
+                let next = self.free.remove(index);
+                self.free[index - 1].end = next.end;
+            }
+            (true, false) => self.free[index - 1].end = range.end,
+            (false, true) => self.free[index].start = range.start,
+            (false, false) => self.free.insert(index, range),
+        }
+    }
+
+    /// Extend the managed size to `size` bytes.
+    pub fn grow(&mut self, size: usize) {
+        assert!(size >= self.size, "The allocator can not shrink");
+        if size == self.size {
+            return;
+        }
+        let old_size = self.size;
+        self.size = size;
+        self.free(BufferSlice {
+            buffer: 0,
+            offset: old_size,
+            size: size - old_size,
+        });
+    }
+
+    /// Return the number of bytes managed by this allocator.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Return the number of free bytes.
+    #[inline]
+    pub fn free_size(&self) -> usize {
+        self.free.iter().map(|range| range.len()).sum()
+    }
+
+    /// Return the size of the largest free range.
+    #[inline]
+    pub fn largest_free(&self) -> usize {
+        self.free.iter().map(|range| range.len()).max().unwrap_or(0)
+    }
+
+    /// Return the free ranges in ascending order.
+    #[inline]
+    pub fn free_ranges(&self) -> &[Range<usize>] {
+        &self.free
+    }
+}
+
+/// Sub-allocator handing out ranges of a few large buffers.
+///
+/// Many meshes can share a buffer and therefore one vertex array object,
+/// and be drawn with base vertex or indirect draw calls.
+///
+/// # Note
+/// When an allocation does not fit, the last buffer is reallocated with a larger size
+/// and the old content is copied over. The buffer returned by `buffer(...)` changes
+/// in that case and has to be attached to vertex arrays again. Once the last buffer
+/// would grow past the maximum capacity, a new buffer is created instead.
+pub struct BufferAllocator<'a> {
+    context: &'a Context,
+    buffers: Vec<(Buffer, RangeAllocator)>,
+    capacity: usize,
+    max_capacity: usize,
+    alignment: usize,
+}
+
+impl<'a> BufferAllocator<'a> {
+    /// Create an allocator of one buffer with `capacity` bytes whose allocations are aligned to `alignment`.
+    ///
+    /// The buffer grows without limit, see `with_max_capacity(...)`.
+    #[inline]
+    pub fn new(context: &'a Context, capacity: usize, alignment: usize) -> Self {
+        Self::with_max_capacity(context, capacity, usize::MAX, alignment)
+    }
+
+    /// Create an allocator whose buffers start with `capacity` bytes and grow up to `max_capacity` bytes.
+    ///
+    /// Allocations larger than `max_capacity` get a buffer of their own.
+    pub fn with_max_capacity(
+        context: &'a Context,
+        capacity: usize,
+        max_capacity: usize,
+        alignment: usize,
+    ) -> Self {
+        assert!(capacity > 0, "The capacity must be greater than 0");
+        assert!(
+            capacity <= max_capacity,
+            "The capacity must not be greater than the maximum capacity"
+        );
+        assert!(alignment > 0, "The alignment must be greater than 0");
+        let buffer = Self::create_buffer(context, capacity);
+        Self {
+            context,
+            buffers: vec![(buffer, RangeAllocator::new(capacity))],
+            capacity,
+            max_capacity,
+            alignment,
+        }
+    }
+
+    /// Allocate `size` bytes, growing the last buffer or creating a new one if needed.
+    ///
+    /// An empty slice is returned for `size == 0` without growing any buffer.
+    pub fn alloc(&mut self, size: usize) -> BufferSlice {
+        let alignment = self.alignment;
+        let found = self
+            .buffers
+            .iter_mut()
+            .enumerate()
+            .find_map(|(index, (_, ranges))| Some((index, ranges.alloc(size, alignment)?)));
+        if let Some((buffer, slice)) = found {
+            return BufferSlice { buffer, ..slice };
+        }
+
+        let capacity = self.buffers.last().map_or(0, |(_, ranges)| ranges.size());
+        let needed = capacity
+            .checked_add(size)
+            .and_then(|needed| needed.checked_add(alignment))
+            .expect("The allocation is too large");
+        if needed <= self.max_capacity {
+            self.reserve((capacity * 2).clamp(needed, self.max_capacity));
+        } else {
+            let buffer = Self::create_buffer(self.context, self.capacity.max(size));
+            self.buffers
+                .push((buffer, RangeAllocator::new(self.capacity.max(size))));
+        }
+        let buffer = self.buffers.len() - 1;
+        let slice = self.buffers[buffer]
+            .1
+            .alloc(size, alignment)
+            .expect("The buffer has been grown to fit the allocation");
+        BufferSlice { buffer, ..slice }
+    }
+
+    /// Allocate space for `data` and upload it.
+    pub fn alloc_with<T: Copy>(&mut self, data: &[T]) -> BufferSlice {
+        let slice = self.alloc(size_of_val(data));
+        self.write(slice, data);
+        slice
+    }
+
+    /// Upload `data` to the start of `slice`.
+    pub fn write<T: Copy>(&self, slice: BufferSlice, data: &[T]) {
+        assert!(
+            size_of_val(data) <= slice.size,
+            "The data is larger than the slice"
+        );
+        if data.is_empty() {
+            return;
+        }
+        unsafe {
+            self.buffer(slice.buffer).sub_data(slice.offset, data);
+        }
+    }
+
+    /// Release `slice`.
+    ///
+    /// # Panics
+    /// If `slice` does not belong to this allocator or was already released.
+    #[inline]
+    pub fn free(&mut self, slice: BufferSlice) {
+        if slice.size == 0 {
+            return;
+        }
+        self.buffers
+            .get_mut(slice.buffer)
+            .expect("The slice does not belong to the allocator")
+            .1
+            .free(slice);
+    }
+
+    /// Grow the last buffer to at least `capacity` bytes, keeping its content.
+    pub fn reserve(&mut self, capacity: usize) {
+        let (old_buffer, ranges) = self.buffers.last_mut().expect("There is always a buffer");
+        let old_capacity = ranges.size();
+        if capacity <= old_capacity {
+            return;
+        }
+        let buffer = Self::create_buffer(self.context, capacity);
+        old_buffer.copy_to(&buffer, 0, 0, old_capacity);
+        *old_buffer = buffer;
+        ranges.grow(capacity);
+    }
+
+    /// Return the buffer at `index`, which is `BufferSlice::buffer`.
+    ///
+    /// # Panics
+    /// If there is no buffer at `index`.
+    #[inline]
+    pub fn buffer(&self, index: usize) -> &Buffer {
+        &self.buffers[index].0
+    }
+
+    /// Return the number of buffers.
+    #[inline]
+    pub fn buffer_count(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Return the total capacity of the buffers in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffers.iter().map(|(_, ranges)| ranges.size()).sum()
+    }
+
+    /// Return the bookkeeping of free ranges of the buffer at `index`.
+    ///
+    /// # Panics
+    /// If there is no buffer at `index`.
+    #[inline]
+    pub fn ranges(&self, index: usize) -> &RangeAllocator {
+        &self.buffers[index].1
+    }
+
+    fn create_buffer(context: &Context, capacity: usize) -> Buffer {
+        let buffer = unsafe { context.new_buffer() };
+        buffer.storage(capacity, BufferFlag::DYNAMIC_STORAGE_BIT);
+        buffer
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt::{Display, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    fn parse(word: &str) -> Option<Self> {
+        match word.to_ascii_lowercase().as_str() {
+            "error" => Some(Severity::Error),
+            "warning" => Some(Severity::Warning),
+            "info" | "note" => Some(Severity::Info),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
+        }
+    }
+}
+
+/// One message of a shader or program info log.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Index of the source string, as passed to `glShaderSource(...)` or set by `#line`.
+    pub source: u32,
+    /// Line number starting from 1, `None` for messages about the whole shader.
+    pub line: Option<u32>,
+    /// Column number starting from 1, only reported by some drivers.
+    pub column: Option<u32>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Parse the info log of a shader or program.
+    ///
+    /// Recognized are the formats of Mesa (`0:12(5): error: ...`),
+    /// NVIDIA (`0(12) : error C0000: ...`) and AMD or Intel (`ERROR: 0:12: ...`).
+    /// Lines which do not start a message continue the previous one or are skipped
+    /// before the first message, and a log without any recognized line becomes a single error.
+    pub fn parse(log: &str) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        for line in log
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+        {
+            match parse_line(line) {
+                Some(diagnostic) if is_summary(&diagnostic) => {}
+                Some(diagnostic) => diagnostics.push(diagnostic),
+                None => {
+                    if let Some(last) = diagnostics.last_mut() {
+                        last.message.push('\n');
+                        last.message.push_str(line);
+                    }
+                }
+            }
+        }
+        if diagnostics.is_empty() && !log.trim().is_empty() {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                source: 0,
+                line: None,
+                column: None,
+                message: log.trim().to_owned(),
+            });
+        }
+        diagnostics
+    }
+
+    /// Format this diagnostic followed by the line of `sources` it points at,
+    /// with a caret under the column or the whole line underlined.
+    ///
+    /// `sources` are indexed by `Diagnostic::source`,
+    /// the snippet is left out if the source or line does not exist.
+    #[inline]
+    pub fn render(&self, sources: &[&str]) -> String {
+        let source = sources.get(self.source as usize).copied();
+        self.render_file(&self.source.to_string(), source)
+    }
+
+    /// Same as `render(...)` with the source given directly and shown as `file`.
+    pub fn render_file(&self, file: &str, source: Option<&str>) -> String {
+        let mut output = format!("{}: {}", self.severity, self.message);
+        let Some(line) = self.line else {
+            return output;
+        };
+        let _ = write!(output, "\n --> {}:{}", file, line);
+        if let Some(column) = self.column {
+            let _ = write!(output, ":{}", column);
+        }
+        let text = source.and_then(|source| source.lines().nth((line as usize).checked_sub(1)?));
+        let Some(text) = text else {
+            return output;
+        };
+        let text = text.trim_end();
+        let number = line.to_string();
+        let gutter = " ".repeat(number.len());
+        let underline = match self.column {
+            Some(column) => {
+                let offset = (column as usize).saturating_sub(1).min(text.len());
+                format!("{}^", " ".repeat(offset))
+            }
+            None => {
+                let content = text.trim_start();
+                let indent = text.len() - content.len();
+                format!("{}{}", " ".repeat(indent), "^".repeat(content.len().max(1)))
+            }
+        };
+        let _ = write!(
+            output,
+            "\n{gutter} |\n{number} | {text}\n{gutter} | {underline}"
+        );
+        output
+    }
+
+    /// Render every diagnostic of `diagnostics` separated by blank lines.
+    pub fn render_all(diagnostics: &[Diagnostic], sources: &[&str]) -> String {
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.render(sources))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}:{}: ", self.source, line, column)?,
+            (Some(line), None) => write!(f, "{}:{}: ", self.source, line)?,
+            _ => {}
+        }
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+fn parse_line(line: &str) -> Option<Diagnostic> {
+    parse_prefixed(line).or_else(|| parse_located(line))
+}
+
+/// AMD and Intel: `ERROR: 0:12: message`, where the location is optional.
+fn parse_prefixed(line: &str) -> Option<Diagnostic> {
+    let (word, rest) = line.split_once(':')?;
+    let severity = Severity::parse(word.trim())?;
+    let rest = rest.trim_start();
+    let location = number(rest).and_then(|(source, rest)| {
+        let (line, rest) = number(rest.strip_prefix(':')?)?;
+        Some((source, line, rest.strip_prefix(':')?))
+    });
+    let (source, line, message) = match location {
+        Some((source, line, message)) => (source, Some(line), message),
+        None => (0, None, rest),
+    };
+    Some(Diagnostic {
+        severity,
+        source,
+        line,
+        column: None,
+        message: message.trim().to_owned(),
+    })
+}
+
+/// Mesa: `0:12(5): error: message` or `0:12(5): preprocessor error: message`,
+/// NVIDIA: `0(12) : error C0000: message`.
+fn parse_located(line: &str) -> Option<Diagnostic> {
+    let (source, rest) = number(line)?;
+    let (line, column, rest) = if let Some(rest) = rest.strip_prefix(':') {
+        let (line, rest) = number(rest)?;
+        match rest.strip_prefix('(') {
+            Some(rest) => {
+                let (column, rest) = number(rest)?;
+                (line, Some(column), rest.strip_prefix(')')?)
+            }
+            None => (line, None, rest),
+        }
+    } else {
+        let (line, rest) = number(rest.strip_prefix('(')?)?;
+        (line, None, rest.strip_prefix(')')?)
+    };
+    let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+    let (head, message) = rest.split_once(':')?;
+    // NVIDIA follows the severity with an error code, the Mesa preprocessor precedes it
+    // with `preprocessor`.
+    let severity = head.split_whitespace().find_map(Severity::parse)?;
+    Some(Diagnostic {
+        severity,
+        source,
+        line: Some(line),
+        column,
+        message: message.trim().to_owned(),
+    })
+}
+
+/// Lines like `ERROR: 2 compilation errors.  No code generated.` only count the others.
+fn is_summary(diagnostic: &Diagnostic) -> bool {
+    diagnostic.line.is_none() && diagnostic.message.contains("No code generated")
+}
+
+fn number(text: &str) -> Option<(u32, &str)> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    Some((text[..end].parse().ok()?, &text[end..]))
+}
@@ -0,0 +1,567 @@
+use std::{collections::HashSet, path::Path};
+
+use ::gltf::{accessor, image, mesh, texture, Document};
+use mats::Mat4;
+
+use crate::{
+    Array, Buffer, Context, GlType, ImageFormat, ImageTarget, InternalFormat, MagFilter, MinFilter,
+    Mode, PixelDataType, SizedFormat, TexParam, TexTarget, Texture, Usage, Wrap,
+};
+
+use super::Aabb;
+
+/// Attribute locations used for the vertex attributes of glTF primitives.
+///
+/// The default matches the layout of `MeshVertex`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GltfLocations {
+    pub position: u32,
+    pub normal: u32,
+    pub tex_coords: [u32; 2],
+    pub tangent: u32,
+    pub color: u32,
+    pub joints: u32,
+    pub weights: u32,
+}
+
+impl Default for GltfLocations {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            position: 0,
+            normal: 1,
+            tex_coords: [2, 4],
+            tangent: 3,
+            color: 5,
+            joints: 6,
+            weights: 7,
+        }
+    }
+}
+
+impl GltfLocations {
+    /// Return the location of `semantic`, `None` for attributes which are not loaded.
+    #[inline]
+    pub fn location(&self, semantic: &mesh::Semantic) -> Option<u32> {
+        match semantic {
+            mesh::Semantic::Positions => Some(self.position),
+            mesh::Semantic::Normals => Some(self.normal),
+            mesh::Semantic::Tangents => Some(self.tangent),
+            mesh::Semantic::TexCoords(set) => self.tex_coords.get(*set as usize).copied(),
+            mesh::Semantic::Colors(0) => Some(self.color),
+            mesh::Semantic::Joints(0) => Some(self.joints),
+            mesh::Semantic::Weights(0) => Some(self.weights),
+            _ => None,
+        }
+    }
+}
+
+/// Texture used by a material, with the set of texture coordinates it reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureRef {
+    /// Index into `GltfScene::textures`.
+    pub texture: usize,
+    pub tex_coord: u32,
+}
+
+impl TextureRef {
+    #[inline]
+    fn new(info: &texture::Info) -> Self {
+        Self {
+            texture: info.texture().index(),
+            tex_coord: info.tex_coord(),
+        }
+    }
+}
+
+/// How the alpha of the base color is used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlphaMode {
+    Opaque,
+    /// Discard fragments whose alpha is below the cutoff.
+    Mask(f32),
+    Blend,
+}
+
+/// Metallic-roughness material of the glTF core specification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PbrMaterial {
+    pub name: Option<String>,
+    pub base_color_factor: [f32; 4],
+    pub base_color_texture: Option<TextureRef>,
+    pub metallic_factor: f32,
+    pub roughness_factor: f32,
+    pub metallic_roughness_texture: Option<TextureRef>,
+    pub normal_texture: Option<TextureRef>,
+    pub normal_scale: f32,
+    pub occlusion_texture: Option<TextureRef>,
+    pub occlusion_strength: f32,
+    pub emissive_factor: [f32; 3],
+    pub emissive_texture: Option<TextureRef>,
+    pub alpha_mode: AlphaMode,
+    pub double_sided: bool,
+}
+
+impl PbrMaterial {
+    fn new(material: &::gltf::Material) -> Self {
+        let pbr = material.pbr_metallic_roughness();
+        let normal = material.normal_texture();
+        let occlusion = material.occlusion_texture();
+        Self {
+            name: material.name().map(str::to_string),
+            base_color_factor: pbr.base_color_factor(),
+            base_color_texture: pbr.base_color_texture().as_ref().map(TextureRef::new),
+            metallic_factor: pbr.metallic_factor(),
+            roughness_factor: pbr.roughness_factor(),
+            metallic_roughness_texture: pbr
+                .metallic_roughness_texture()
+                .as_ref()
+                .map(TextureRef::new),
+            normal_texture: normal.as_ref().map(|normal| TextureRef {
+                texture: normal.texture().index(),
+                tex_coord: normal.tex_coord(),
+            }),
+            normal_scale: normal.as_ref().map_or(1.0, |normal| normal.scale()),
+            occlusion_texture: occlusion.as_ref().map(|occlusion| TextureRef {
+                texture: occlusion.texture().index(),
+                tex_coord: occlusion.tex_coord(),
+            }),
+            occlusion_strength: occlusion
+                .as_ref()
+                .map_or(1.0, |occlusion| occlusion.strength()),
+            emissive_factor: material.emissive_factor(),
+            emissive_texture: material.emissive_texture().as_ref().map(TextureRef::new),
+            alpha_mode: match material.alpha_mode() {
+                ::gltf::material::AlphaMode::Opaque => AlphaMode::Opaque,
+                ::gltf::material::AlphaMode::Mask => {
+                    AlphaMode::Mask(material.alpha_cutoff().unwrap_or(0.5))
+                }
+                ::gltf::material::AlphaMode::Blend => AlphaMode::Blend,
+            },
+            double_sided: material.double_sided(),
+        }
+    }
+}
+
+/// Vertex array of one glTF primitive, reading from the buffers of the scene.
+pub struct GltfPrimitive {
+    pub array: Array,
+    pub mode: Mode,
+    /// Number of indices, or of vertices for primitives without indices.
+    pub count: usize,
+    /// Type and byte offset of the indices in the element buffer.
+    pub indices: Option<(GlType, usize)>,
+    /// Index into `GltfScene::materials`, `None` for the default material.
+    pub material: Option<usize>,
+    pub bounds: Aabb,
+}
+
+impl GltfPrimitive {
+    /// Draw the primitive.
+    #[inline]
+    pub fn draw(&self, context: &Context) {
+        self.array.bind();
+        match self.indices {
+            Some((ty, offset)) => context.draw_elements(self.mode, self.count, ty, offset),
+            None => context.draw_arrays(self.mode, 0, self.count as _),
+        }
+    }
+}
+
+/// Mesh made of primitives.
+pub struct GltfMesh {
+    pub name: Option<String>,
+    pub primitives: Vec<GltfPrimitive>,
+}
+
+/// Node of the scene hierarchy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfNode {
+    pub name: Option<String>,
+    /// Transform relative to the parent.
+    pub transform: Mat4<f32>,
+    /// Index into `GltfScene::meshes`.
+    pub mesh: Option<usize>,
+    /// Indices into `GltfScene::nodes`.
+    pub children: Vec<usize>,
+}
+
+/// Materials and node hierarchy of a glTF document, which need no OpenGL context.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfSceneInfo {
+    pub materials: Vec<PbrMaterial>,
+    pub nodes: Vec<GltfNode>,
+    /// Root nodes of the default scene.
+    pub roots: Vec<usize>,
+}
+
+impl GltfSceneInfo {
+    /// Parse the content of a `.gltf` or `.glb` file without loading its buffers or images.
+    pub fn parse(data: &[u8]) -> Result<Self, String> {
+        let gltf = ::gltf::Gltf::from_slice(data).map_err(|e| e.to_string())?;
+        Ok(Self::new(&gltf.document))
+    }
+
+    fn new(document: &Document) -> Self {
+        let materials = document.materials().map(|m| PbrMaterial::new(&m)).collect();
+        let nodes = document
+            .nodes()
+            .map(|node| GltfNode {
+                name: node.name().map(str::to_string),
+                transform: Mat4::from(node.transform().matrix()).transpose(),
+                mesh: node.mesh().map(|mesh| mesh.index()),
+                children: node.children().map(|child| child.index()).collect(),
+            })
+            .collect();
+        let roots = document
+            .default_scene()
+            .or_else(|| document.scenes().next())
+            .map(|scene| scene.nodes().map(|node| node.index()).collect())
+            .unwrap_or_default();
+        Self {
+            materials,
+            nodes,
+            roots,
+        }
+    }
+}
+
+/// Scene loaded from a `.gltf` or `.glb` file.
+///
+/// Every glTF buffer becomes one `Buffer` shared by all primitives,
+/// and every glTF texture one `Texture` with the parameters of its sampler.
+pub struct GltfScene {
+    pub buffers: Vec<Buffer>,
+    pub textures: Vec<Texture>,
+    pub materials: Vec<PbrMaterial>,
+    pub meshes: Vec<GltfMesh>,
+    pub nodes: Vec<GltfNode>,
+    /// Root nodes of the default scene.
+    pub roots: Vec<usize>,
+}
+
+impl GltfScene {
+    /// Load the file at `path` with the default attribute locations.
+    #[inline]
+    pub fn load<P: AsRef<Path>>(context: &Context, path: P) -> Result<Self, String> {
+        Self::load_with(context, path, &GltfLocations::default())
+    }
+
+    /// Load the file at `path`, putting the vertex attributes at `locations`.
+    pub fn load_with<P: AsRef<Path>>(
+        context: &Context,
+        path: P,
+        locations: &GltfLocations,
+    ) -> Result<Self, String> {
+        let path = path.as_ref();
+        let (document, buffers, images) = ::gltf::import(path)
+            .map_err(|e| format!("Failed to load '{}': {}", path.display(), e))?;
+
+        let buffers: Vec<Buffer> = buffers
+            .iter()
+            .map(|data| {
+                let buffer = unsafe { context.new_buffer() };
+                buffer.data(&data.0, Usage::StaticDraw);
+                buffer
+            })
+            .collect();
+        let textures = Self::load_textures(context, &document, &images)?;
+
+        let mut meshes = Vec::new();
+        for mesh in document.meshes() {
+            let primitives = mesh
+                .primitives()
+                .map(|primitive| Self::load_primitive(context, &buffers, &primitive, locations))
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("Mesh {}: {}", mesh.index(), e))?;
+            meshes.push(GltfMesh {
+                name: mesh.name().map(str::to_string),
+                primitives,
+            });
+        }
+
+        let GltfSceneInfo {
+            materials,
+            nodes,
+            roots,
+        } = GltfSceneInfo::new(&document);
+        Ok(Self {
+            buffers,
+            textures,
+            materials,
+            meshes,
+            nodes,
+            roots,
+        })
+    }
+
+    /// Return the transform of every node relative to the scene.
+    ///
+    /// Nodes outside the default scene keep their local transform.
+    pub fn world_transforms(&self) -> Vec<Mat4<f32>> {
+        let mut transforms: Vec<_> = self.nodes.iter().map(|node| node.transform).collect();
+        let mut stack: Vec<_> = self.roots.clone();
+        while let Some(index) = stack.pop() {
+            for &child in &self.nodes[index].children {
+                transforms[child] = transforms[index] * self.nodes[child].transform;
+                stack.push(child);
+            }
+        }
+        transforms
+    }
+
+    /// Draw every primitive of the default scene.
+    ///
+    /// `prepare` is called before each primitive with its world transform and material,
+    /// to set the uniforms of the program in use.
+    pub fn draw<F>(&self, context: &Context, mut prepare: F)
+    where
+        F: FnMut(&Mat4<f32>, Option<&PbrMaterial>),
+    {
+        let transforms = self.world_transforms();
+        let mut stack: Vec<_> = self.roots.clone();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if let Some(mesh) = node.mesh {
+                for primitive in &self.meshes[mesh].primitives {
+                    let material = primitive.material.map(|index| &self.materials[index]);
+                    prepare(&transforms[index], material);
+                    primitive.draw(context);
+                }
+            }
+            stack.extend(&node.children);
+        }
+    }
+
+    fn load_primitive(
+        context: &Context,
+        buffers: &[Buffer],
+        primitive: &mesh::Primitive,
+        locations: &GltfLocations,
+    ) -> Result<GltfPrimitive, String> {
+        let array = unsafe { context.new_array() };
+        let mut count = 0;
+        for (semantic, accessor) in primitive.attributes() {
+            let Some(location) = locations.location(&semantic) else {
+                continue;
+            };
+            let (view, offset) = Self::view(&accessor)?;
+            let ty = Self::gl_type(accessor.data_type());
+            let size = accessor.dimensions().multiplicity();
+            let stride = view.stride().unwrap_or(accessor.size());
+            array.vertex_buffer(location, &buffers[view.buffer().index()], offset, stride);
+            if matches!(semantic, mesh::Semantic::Joints(_)) {
+                array.attrib_i_format(location, ty, size, 0);
+            } else {
+                array.attrib_format(location, ty, size, accessor.normalized(), 0);
+            }
+            array.attrib_binding(location, location);
+            array.enable_attrib(location);
+            if semantic == mesh::Semantic::Positions {
+                count = accessor.count();
+            }
+        }
+
+        let indices = match primitive.indices() {
+            Some(accessor) => {
+                let (view, offset) = Self::view(&accessor)?;
+                array.element_buffer(&buffers[view.buffer().index()]);
+                count = accessor.count();
+                Some((Self::gl_type(accessor.data_type()), offset))
+            }
+            None => None,
+        };
+
+        let bounds = primitive.bounding_box();
+        Ok(GltfPrimitive {
+            array,
+            mode: match primitive.mode() {
+                mesh::Mode::Points => Mode::Points,
+                mesh::Mode::Lines => Mode::Lines,
+                mesh::Mode::LineLoop => Mode::LineLoop,
+                mesh::Mode::LineStrip => Mode::LineStrip,
+                mesh::Mode::Triangles => Mode::Triangles,
+                mesh::Mode::TriangleStrip => Mode::TriangleStrip,
+                mesh::Mode::TriangleFan => Mode::TriangleFan,
+            },
+            count,
+            indices,
+            material: primitive.material().index(),
+            bounds: Aabb {
+                min: bounds.min,
+                max: bounds.max,
+            },
+        })
+    }
+
+    /// Return the buffer view of `accessor` and the byte offset of its first element.
+    fn view<'a>(
+        accessor: &accessor::Accessor<'a>,
+    ) -> Result<(::gltf::buffer::View<'a>, usize), String> {
+        if accessor.sparse().is_some() {
+            return Err(format!("Accessor {} is sparse", accessor.index()));
+        }
+        let view = accessor
+            .view()
+            .ok_or_else(|| format!("Accessor {} has no buffer view", accessor.index()))?;
+        let offset = view.offset() + accessor.offset();
+        Ok((view, offset))
+    }
+
+    #[inline]
+    fn gl_type(ty: accessor::DataType) -> GlType {
+        match ty {
+            accessor::DataType::I8 => GlType::i8,
+            accessor::DataType::U8 => GlType::u8,
+            accessor::DataType::I16 => GlType::i16,
+            accessor::DataType::U16 => GlType::u16,
+            accessor::DataType::U32 => GlType::u32,
+            accessor::DataType::F32 => GlType::f32,
+        }
+    }
+
+    fn load_textures(
+        context: &Context,
+        document: &Document,
+        images: &[image::Data],
+    ) -> Result<Vec<Texture>, String> {
+        // Colors are stored in sRGB, every other texture holds linear data.
+        let srgb: HashSet<usize> = document
+            .materials()
+            .flat_map(|material| {
+                [
+                    material.pbr_metallic_roughness().base_color_texture(),
+                    material.emissive_texture(),
+                ]
+            })
+            .flatten()
+            .map(|info| info.texture().index())
+            .collect();
+
+        context.unpack_alignment(1);
+        let textures = document
+            .textures()
+            .map(|texture| {
+                let image = &images[texture.source().index()];
+                Self::load_texture(context, &texture, image, srgb.contains(&texture.index()))
+                    .map_err(|e| format!("Texture {}: {}", texture.index(), e))
+            })
+            .collect();
+        // Restore the state before returning any error.
+        Texture::unbind(TexTarget::Tex2D);
+        context.unpack_alignment(4);
+        textures
+    }
+
+    /// Create the texture of `gltf_texture` from `image`, leaving it bound to `Tex2D`.
+    fn load_texture(
+        context: &Context,
+        gltf_texture: &texture::Texture,
+        image: &image::Data,
+        srgb: bool,
+    ) -> Result<Texture, String> {
+        let (internal_format, format, ty) = Self::image_format(image.format, srgb);
+        let texture = context.new_texture(TexTarget::Tex2D);
+        texture.bind(TexTarget::Tex2D);
+        Texture::load(
+            ImageTarget::Tex2d,
+            internal_format,
+            (image.width, image.height),
+            format,
+            ty,
+            &image.pixels,
+        )?;
+
+        let sampler = gltf_texture.sampler();
+        let min_filter = match sampler.min_filter() {
+            Some(texture::MinFilter::Nearest) => MinFilter::Nearest,
+            Some(texture::MinFilter::Linear) => MinFilter::Linear,
+            Some(texture::MinFilter::NearestMipmapNearest) => MinFilter::NearestMipmapNearest,
+            Some(texture::MinFilter::LinearMipmapNearest) => MinFilter::LinearMipmapNearest,
+            Some(texture::MinFilter::NearestMipmapLinear) => MinFilter::NearestMipmapLinear,
+            Some(texture::MinFilter::LinearMipmapLinear) | None => MinFilter::LinearMipmapLinear,
+        };
+        texture.set(TexParam::MinFilter(min_filter));
+        texture.set(TexParam::MagFilter(match sampler.mag_filter() {
+            Some(texture::MagFilter::Nearest) => MagFilter::Nearest,
+            Some(texture::MagFilter::Linear) | None => MagFilter::Linear,
+        }));
+        texture.set(TexParam::WrapS(Self::wrap(sampler.wrap_s())));
+        texture.set(TexParam::WrapT(Self::wrap(sampler.wrap_t())));
+        if !matches!(min_filter, MinFilter::Nearest | MinFilter::Linear) {
+            texture.gen_minmap();
+        }
+        Ok(texture)
+    }
+
+    #[inline]
+    fn wrap(mode: texture::WrappingMode) -> Wrap {
+        match mode {
+            texture::WrappingMode::ClampToEdge => Wrap::ClampToEdge,
+            texture::WrappingMode::MirroredRepeat => Wrap::MirroredRepeat,
+            texture::WrappingMode::Repeat => Wrap::Repeat,
+        }
+    }
+
+    fn image_format(
+        format: image::Format,
+        srgb: bool,
+    ) -> (InternalFormat, ImageFormat, PixelDataType) {
+        use image::Format;
+        let sized = InternalFormat::Sized;
+        match format {
+            Format::R8 => (sized(SizedFormat::R8), ImageFormat::Red, PixelDataType::u8),
+            Format::R8G8 => (sized(SizedFormat::RG8), ImageFormat::RG, PixelDataType::u8),
+            Format::R8G8B8 if srgb => (
+                sized(SizedFormat::SRGB8),
+                ImageFormat::RGB,
+                PixelDataType::u8,
+            ),
+            Format::R8G8B8 => (
+                sized(SizedFormat::RGB8),
+                ImageFormat::RGB,
+                PixelDataType::u8,
+            ),
+            Format::R8G8B8A8 if srgb => (
+                sized(SizedFormat::SRGB8_ALPHA8),
+                ImageFormat::RGBA,
+                PixelDataType::u8,
+            ),
+            Format::R8G8B8A8 => (
+                sized(SizedFormat::RGBA8),
+                ImageFormat::RGBA,
+                PixelDataType::u8,
+            ),
+            Format::R16 => (
+                sized(SizedFormat::R16),
+                ImageFormat::Red,
+                PixelDataType::u16,
+            ),
+            Format::R16G16 => (
+                sized(SizedFormat::RG16),
+                ImageFormat::RG,
+                PixelDataType::u16,
+            ),
+            Format::R16G16B16 => (
+                InternalFormat::Base(crate::BaseFormat::RGB),
+                ImageFormat::RGB,
+                PixelDataType::u16,
+            ),
+            Format::R16G16B16A16 => (
+                sized(SizedFormat::RGBA16),
+                ImageFormat::RGBA,
+                PixelDataType::u16,
+            ),
+            Format::R32G32B32FLOAT => (
+                sized(SizedFormat::RGB32F),
+                ImageFormat::RGB,
+                PixelDataType::f32,
+            ),
+            Format::R32G32B32A32FLOAT => (
+                sized(SizedFormat::RGBA32F),
+                ImageFormat::RGBA,
+                PixelDataType::f32,
+            ),
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{Context, Program, ShaderType, Uniform, UniformValue};
+
+use super::{
+    Diagnostic, DirectoryProvider, FileProvider, Preprocessed, Preprocessor, ProgramBuilder,
+    ProgramError,
+};
+
+/// Files and their modification times when they were watched.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WatchedFiles {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl WatchedFiles {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Watch `path` from its current modification time on, which may not exist yet.
+    pub fn watch(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let modified = modified_time(&path);
+        match self.files.iter_mut().find(|(file, _)| *file == path) {
+            Some((_, time)) => *time = modified,
+            None => self.files.push((path, modified)),
+        }
+    }
+
+    /// Watch the files of `other` which are not watched yet, from their current modification time on.
+    pub fn extend(&mut self, other: &WatchedFiles) {
+        for path in other.paths() {
+            if !self.contains(path) {
+                self.watch(path);
+            }
+        }
+    }
+
+    /// Return whether any file was modified, created or removed since it was watched.
+    pub fn changed(&self) -> bool {
+        self.files
+            .iter()
+            .any(|(path, modified)| *modified != modified_time(path))
+    }
+
+    #[inline]
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.iter().any(|(file, _)| file == path)
+    }
+
+    #[inline]
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+}
+
+/// Stages of a `HotProgram` read from disk and the files they were read from.
+///
+/// Every stage is read through a `Preprocessor` rooted at the directory of the stage.
+/// This part needs no OpenGL context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotSources {
+    stages: Vec<(ShaderType, PathBuf)>,
+    watched: WatchedFiles,
+}
+
+impl HotSources {
+    /// Watch the files of `stages`, which are read by `read()`.
+    pub fn new<P: AsRef<Path>>(stages: &[(ShaderType, P)]) -> Self {
+        let mut watched = WatchedFiles::new();
+        let stages = stages
+            .iter()
+            .map(|(ty, path)| {
+                watched.watch(path.as_ref());
+                (*ty, path.as_ref().to_path_buf())
+            })
+            .collect();
+        Self { stages, watched }
+    }
+
+    #[inline]
+    pub fn stages(&self) -> &[(ShaderType, PathBuf)] {
+        &self.stages
+    }
+
+    /// Return the files read by the last `read()`.
+    #[inline]
+    pub fn watched(&self) -> &WatchedFiles {
+        &self.watched
+    }
+
+    /// Return whether any watched file was modified since the last `read()`.
+    #[inline]
+    pub fn changed(&self) -> bool {
+        self.watched.changed()
+    }
+
+    /// Read and preprocess every stage, in the order of `stages()`.
+    ///
+    /// Every file asked for is watched afterwards, including the ones which failed to read.
+    /// When a stage fails, the files watched before stay watched too, so fixing an include
+    /// which was not reached this time still counts as a change.
+    pub fn read(&mut self) -> Result<Vec<Preprocessed>, String> {
+        let watched = RefCell::new(WatchedFiles::new());
+        let mut outputs = Vec::new();
+        let mut errors = Vec::new();
+        for (_, path) in &self.stages {
+            watched.borrow_mut().watch(path);
+            let directory = path.parent().unwrap_or(Path::new(""));
+            let file = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let provider = Recording {
+                provider: DirectoryProvider::new(directory),
+                directory,
+                watched: &watched,
+            };
+            match Preprocessor::new(provider).process(&file) {
+                Ok(output) => outputs.push(output),
+                Err(err) => errors.push(format!("{}: {}", path.display(), err)),
+            }
+        }
+        let mut watched = watched.into_inner();
+        if errors.is_empty() {
+            self.watched = watched;
+            return Ok(outputs);
+        }
+        watched.extend(&self.watched);
+        self.watched = watched;
+        Err(errors.join("\n"))
+    }
+}
+
+/// Provider watching every file it is asked for before reading it.
+struct Recording<'a> {
+    provider: DirectoryProvider,
+    directory: &'a Path,
+    watched: &'a RefCell<WatchedFiles>,
+}
+
+impl FileProvider for Recording<'_> {
+    fn read(&self, path: &str) -> Result<String, String> {
+        self.watched.borrow_mut().watch(self.directory.join(path));
+        self.provider.read(path)
+    }
+}
+
+/// Program rebuilt from its files on disk whenever one of them changes.
+///
+/// Every stage is read through a `Preprocessor` rooted at the directory of the stage,
+/// so `#include`d files are watched too, see `HotSources`. When a rebuild fails the previous
+/// program is kept and the error is available from `error()` until the next successful build.
+///
+/// ```ignore
+/// let mut program = HotProgram::new(&context, &[
+///     (ShaderType::Vertex, "shader/mesh.vs"),
+///     (ShaderType::Fragmet, "shader/mesh.fs"),
+/// ])?;
+/// loop {
+///     if program.reload_if_changed() {
+///         println!("Shader reloaded");
+///     } else if let Some(err) = program.error() {
+///         eprintln!("{}", err);
+///     }
+///     program.set_uniform("time", &time)?;
+///     program.using();
+/// }
+/// ```
+pub struct HotProgram<'a> {
+    context: &'a Context,
+    sources: HotSources,
+    program: Program,
+    /// Values set by `set_uniform(...)` and their handles, given again to every rebuilt program.
+    uniforms: HashMap<String, Box<dyn HotUniform>>,
+    error: Option<String>,
+}
+
+impl<'a> HotProgram<'a> {
+    /// Build a program from the files of `stages`.
+    ///
+    /// It will return `Err(String)` if the first build fails.
+    pub fn new<P: AsRef<Path>>(
+        context: &'a Context,
+        stages: &[(ShaderType, P)],
+    ) -> Result<Self, String> {
+        let mut sources = HotSources::new(stages);
+        let program = build(context, &mut sources)?;
+        Ok(Self {
+            context,
+            sources,
+            program,
+            uniforms: HashMap::new(),
+            error: None,
+        })
+    }
+
+    /// Return the current program, which is the last one built successfully.
+    #[inline]
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Use the current program in the current context.
+    #[inline]
+    pub fn using(&self) {
+        self.program.using();
+    }
+
+    /// Return the error of the last rebuild, if it failed.
+    #[inline]
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Return the stages and the files read by the last build.
+    #[inline]
+    pub fn sources(&self) -> &HotSources {
+        &self.sources
+    }
+
+    /// Return whether any watched file was modified since the last build.
+    #[inline]
+    pub fn changed(&self) -> bool {
+        self.sources.changed()
+    }
+
+    /// Rebuild the program if any watched file was modified.
+    ///
+    /// It will return `true` if the program was replaced.
+    pub fn reload_if_changed(&mut self) -> bool {
+        self.changed() && self.reload().is_ok()
+    }
+
+    /// Rebuild the program and replace the current one if the build succeeds.
+    ///
+    /// The uniform values set by `set_uniform(...)` are given to the new program,
+    /// uniforms which do not exist anymore are skipped.
+    pub fn reload(&mut self) -> Result<(), String> {
+        match build(self.context, &mut self.sources) {
+            Ok(program) => {
+                for (name, uniform) in &mut self.uniforms {
+                    uniform.rebind(&program, name);
+                }
+                self.program = program;
+                self.error = None;
+                Ok(())
+            }
+            Err(err) => {
+                self.error = Some(err.clone());
+                Err(err)
+            }
+        }
+    }
+
+    /// Set the uniform `name` of the current program and remember the value for rebuilds.
+    ///
+    /// The handle from `Program::uniform(...)` is kept, so setting the same uniform
+    /// again, e.g. once per frame, is a single `glProgramUniform*(...)` call until the next rebuild.
+    pub fn set_uniform<T>(&mut self, name: &str, value: &T) -> Result<(), String>
+    where
+        T: UniformValue + Clone + 'static,
+    {
+        let cached = self
+            .uniforms
+            .get_mut(name)
+            .and_then(|uniform| uniform.as_any_mut().downcast_mut::<CachedUniform<T>>());
+        if let Some(cached) = cached {
+            cached.value.clone_from(value);
+            let handle = cached.handle.as_ref().map_err(Clone::clone)?;
+            handle.set(value);
+            return Ok(());
+        }
+        let handle = self.program.uniform::<T>(name)?;
+        handle.set(value);
+        let cached = CachedUniform {
+            handle: Ok(handle),
+            value: value.clone(),
+        };
+        self.uniforms.insert(name.to_owned(), Box::new(cached));
+        Ok(())
+    }
+}
+
+/// Value set by `HotProgram::set_uniform(...)` and its handle in the current program.
+struct CachedUniform<T> {
+    /// The error if the current program has no such uniform.
+    handle: Result<Uniform<T>, String>,
+    value: T,
+}
+
+trait HotUniform {
+    /// Look the uniform up in the rebuilt `program` and give it the value again.
+    fn rebind(&mut self, program: &Program, name: &str);
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: UniformValue + 'static> HotUniform for CachedUniform<T> {
+    fn rebind(&mut self, program: &Program, name: &str) {
+        self.handle = program.uniform::<T>(name);
+        if let Ok(handle) = &self.handle {
+            handle.set(&self.value);
+        }
+    }
+
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Read the files of `sources` and build a program from them.
+fn build(context: &Context, sources: &mut HotSources) -> Result<Program, String> {
+    let outputs = sources.read()?;
+    let mut builder = ProgramBuilder::new(context);
+    for ((ty, _), output) in sources.stages().iter().zip(&outputs) {
+        builder = builder.stage(*ty, output.code.as_str());
+    }
+    builder.build().map_err(|err| match err {
+        ProgramError::Compile(logs) => logs
+            .iter()
+            .map(|(ty, log)| {
+                let ((_, path), output) = sources
+                    .stages()
+                    .iter()
+                    .zip(&outputs)
+                    .find(|((stage, _), _)| stage == ty)
+                    .expect("every log belongs to a stage");
+                render(path, output, log)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        err => err.to_string(),
+    })
+}
+
+/// Render the compile log of the stage at `path` against the files it was built from.
+fn render(path: &Path, output: &Preprocessed, log: &str) -> String {
+    let diagnostics = Diagnostic::parse(log);
+    format!(
+        "{} failed to compile:\n{}",
+        path.display(),
+        output.render_all(&diagnostics)
+    )
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,387 @@
+use std::ops::Range;
+
+use crate::{Array, Buffer, Context, GlType, Mode, Usage, VertexLayout};
+
+/// Common vertex with a position, a normal, texture coordinates and a tangent.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, VertexLayout)]
+pub struct MeshVertex {
+    #[vertex(position)]
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+    /// Direction of increasing `u`, `w` is the sign of the bitangent `cross(normal, tangent)`.
+    pub tangent: [f32; 4],
+}
+
+/// Index data of a mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indices<'a> {
+    U16(&'a [u16]),
+    U32(&'a [u32]),
+}
+
+impl Indices<'_> {
+    /// Return the number of indices.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Indices::U16(indices) => indices.len(),
+            Indices::U32(indices) => indices.len(),
+        }
+    }
+
+    /// Return `true` if there are no indices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the type of each index.
+    #[inline]
+    pub fn ty(&self) -> GlType {
+        match self {
+            Indices::U16(_) => GlType::u16,
+            Indices::U32(_) => GlType::u32,
+        }
+    }
+
+    #[inline]
+    fn upload(&self, buffer: &Buffer, usage: Usage) {
+        match self {
+            Indices::U16(indices) => buffer.data(indices, usage),
+            Indices::U32(indices) => buffer.data(indices, usage),
+        }
+    }
+}
+
+/// Axis aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl Aabb {
+    /// The box containing nothing, which is the identity of `union(...)`.
+    pub const EMPTY: Self = Self {
+        min: [f32::INFINITY; 3],
+        max: [f32::NEG_INFINITY; 3],
+    };
+
+    /// Return the smallest box containing all `points`.
+    pub fn from_points<I: IntoIterator<Item = [f32; 3]>>(points: I) -> Self {
+        points.into_iter().fold(Self::EMPTY, |aabb, point| {
+            aabb.union(&Self {
+                min: point,
+                max: point,
+            })
+        })
+    }
+
+    /// Return the smallest box containing the positions of `vertices`.
+    ///
+    /// It will return `None` if a vertex has no position, see `VertexLayout::position()`.
+    pub fn from_vertices<V: VertexLayout>(vertices: &[V]) -> Option<Self> {
+        vertices.iter().try_fold(Self::EMPTY, |aabb, vertex| {
+            let point = vertex.position()?;
+            Some(aabb.union(&Self {
+                min: point,
+                max: point,
+            }))
+        })
+    }
+
+    /// Return the smallest box containing both boxes.
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: std::array::from_fn(|i| self.min[i].min(other.min[i])),
+            max: std::array::from_fn(|i| self.max[i].max(other.max[i])),
+        }
+    }
+
+    /// Return `true` if the box contains nothing.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        (0..3).any(|i| self.min[i] > self.max[i])
+    }
+
+    /// Return the center of the box.
+    #[inline]
+    pub fn center(&self) -> [f32; 3] {
+        std::array::from_fn(|i| (self.min[i] + self.max[i]) * 0.5)
+    }
+
+    /// Return the size of the box along each axis.
+    #[inline]
+    pub fn size(&self) -> [f32; 3] {
+        std::array::from_fn(|i| self.max[i] - self.min[i])
+    }
+}
+
+/// Range of a mesh drawn by one call, e.g. the part using one material.
+///
+/// The range counts indices for indexed meshes and vertices otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubMesh {
+    pub first: usize,
+    pub count: usize,
+}
+
+impl From<Range<usize>> for SubMesh {
+    #[inline]
+    fn from(range: Range<usize>) -> Self {
+        Self {
+            first: range.start,
+            count: range.len(),
+        }
+    }
+}
+
+/// Mesh Object
+///
+/// It owns a vertex array with one vertex buffer and an optional index buffer,
+/// and remembers everything needed to draw them.
+pub struct Mesh {
+    array: Array,
+    vertices: Buffer,
+    indices: Option<(Buffer, GlType)>,
+    mode: Mode,
+    usage: Usage,
+    stride: usize,
+    vertex_count: usize,
+    index_count: usize,
+    sub_meshes: Vec<SubMesh>,
+    bounds: Option<Aabb>,
+}
+
+impl Mesh {
+    /// Create a mesh of static data.
+    #[inline]
+    pub fn new<V: VertexLayout + Copy>(
+        context: &Context,
+        vertices: &[V],
+        indices: Option<Indices>,
+        mode: Mode,
+    ) -> Self {
+        Self::with_usage(context, vertices, indices, mode, Usage::StaticDraw)
+    }
+
+    /// Create a mesh whose buffers are allocated with `usage`,
+    /// e.g. `Usage::DynamicDraw` for data updated in place.
+    pub fn with_usage<V: VertexLayout + Copy>(
+        context: &Context,
+        vertices: &[V],
+        indices: Option<Indices>,
+        mode: Mode,
+        usage: Usage,
+    ) -> Self {
+        let array = unsafe { context.new_array() };
+        let vertex_buffer = unsafe { context.new_buffer() };
+        vertex_buffer.data(vertices, usage);
+        array.vertex_buffer(0, &vertex_buffer, 0, V::STRIDE);
+        array.layout::<V>(0);
+
+        let mut mesh = Self {
+            array,
+            vertices: vertex_buffer,
+            indices: None,
+            mode,
+            usage,
+            stride: V::STRIDE,
+            vertex_count: vertices.len(),
+            index_count: 0,
+            sub_meshes: Vec::new(),
+            bounds: Aabb::from_vertices(vertices),
+        };
+        if let Some(indices) = indices {
+            let index_buffer = unsafe { context.new_buffer() };
+            mesh.array.element_buffer(&index_buffer);
+            mesh.indices = Some((index_buffer, indices.ty()));
+            mesh.set_indices(indices);
+        }
+        mesh
+    }
+
+    /// Draw the whole mesh.
+    #[inline]
+    pub fn draw(&self, context: &Context) {
+        self.draw_range(context, 0, self.element_count(), 1);
+    }
+
+    /// Draw `instances` instances of the whole mesh.
+    #[inline]
+    pub fn draw_instanced(&self, context: &Context, instances: usize) {
+        self.draw_range(context, 0, self.element_count(), instances);
+    }
+
+    /// Draw the sub-mesh at `index`.
+    ///
+    /// # Panics
+    /// If there is no sub-mesh at `index`.
+    #[inline]
+    pub fn draw_sub_mesh(&self, context: &Context, index: usize) {
+        let sub_mesh = self.sub_meshes[index];
+        self.draw_range(context, sub_mesh.first, sub_mesh.count, 1);
+    }
+
+    fn draw_range(&self, context: &Context, first: usize, count: usize, instances: usize) {
+        self.array.bind();
+        match &self.indices {
+            Some((_, ty)) => {
+                let offset = first * ty.size();
+                if instances == 1 {
+                    context.draw_elements(self.mode, count, *ty, offset);
+                } else {
+                    context.draw_elements_instanced(self.mode, count, *ty, offset, instances);
+                }
+            }
+            None => {
+                if instances == 1 {
+                    context.draw_arrays(self.mode, first as _, count as _);
+                } else {
+                    context.draw_arrays_instanced(
+                        self.mode,
+                        first as _,
+                        count as _,
+                        instances as _,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Overwrite the vertices starting from the vertex `first` with `vertices`.
+    ///
+    /// The bounding box is grown to contain `vertices` but never shrinks,
+    /// as the replaced vertices are unknown. Use `set_bounds(...)` for an exact box.
+    ///
+    /// # Panics
+    /// If `V` is not the vertex type of this mesh or the range is out of bounds.
+    pub fn update_vertices<V: VertexLayout + Copy>(&mut self, first: usize, vertices: &[V]) {
+        assert_eq!(V::STRIDE, self.stride, "The vertex type does not match");
+        assert!(
+            first + vertices.len() <= self.vertex_count,
+            "The vertices are out of bounds"
+        );
+        unsafe {
+            self.vertices.sub_data(first * self.stride, vertices);
+        }
+        if let (Some(bounds), Some(update)) = (self.bounds, Aabb::from_vertices(vertices)) {
+            self.bounds = Some(bounds.union(&update));
+        }
+    }
+
+    /// Replace all vertices, the number of vertices can change.
+    ///
+    /// # Panics
+    /// If `V` is not the vertex type of this mesh.
+    pub fn set_vertices<V: VertexLayout + Copy>(&mut self, vertices: &[V]) {
+        assert_eq!(V::STRIDE, self.stride, "The vertex type does not match");
+        self.vertices.data(vertices, self.usage);
+        self.vertex_count = vertices.len();
+        self.bounds = Aabb::from_vertices(vertices);
+    }
+
+    /// Replace all indices, the number of indices can change.
+    ///
+    /// # Panics
+    /// If the mesh was created without indices or the index type differs.
+    pub fn set_indices(&mut self, indices: Indices) {
+        let (buffer, ty) = self
+            .indices
+            .as_ref()
+            .expect("The mesh was created without indices");
+        assert_eq!(*ty, indices.ty(), "The index type does not match");
+        indices.upload(buffer, self.usage);
+        self.index_count = indices.len();
+    }
+
+    /// Return the number of vertices.
+    #[inline]
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// Return the number of indices, 0 for meshes without indices.
+    #[inline]
+    pub fn index_count(&self) -> usize {
+        self.index_count
+    }
+
+    /// Return the type of the indices, if the mesh has indices.
+    #[inline]
+    pub fn index_type(&self) -> Option<GlType> {
+        self.indices.as_ref().map(|&(_, ty)| ty)
+    }
+
+    /// Return the primitive mode.
+    #[inline]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Set the primitive mode.
+    #[inline]
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    /// Return the sub-meshes.
+    #[inline]
+    pub fn sub_meshes(&self) -> &[SubMesh] {
+        &self.sub_meshes
+    }
+
+    /// Append a sub-mesh and return its index.
+    #[inline]
+    pub fn add_sub_mesh(&mut self, sub_mesh: impl Into<SubMesh>) -> usize {
+        self.sub_meshes.push(sub_mesh.into());
+        self.sub_meshes.len() - 1
+    }
+
+    /// Return the bounding box.
+    ///
+    /// It is computed by `Aabb::from_vertices(...)` when the vertices are set,
+    /// so it is `None` for vertex types without a position unless set by `set_bounds(...)`.
+    #[inline]
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.bounds
+    }
+
+    /// Set the bounding box, e.g. from `Aabb::from_points(...)`.
+    ///
+    /// It is replaced when the vertices are set again.
+    #[inline]
+    pub fn set_bounds(&mut self, bounds: Aabb) {
+        self.bounds = Some(bounds);
+    }
+
+    /// Return its array.
+    #[inline]
+    pub fn array(&self) -> &Array {
+        &self.array
+    }
+
+    /// Return its vertex buffer.
+    #[inline]
+    pub fn vertex_buffer(&self) -> &Buffer {
+        &self.vertices
+    }
+
+    /// Return its index buffer, if the mesh has indices.
+    #[inline]
+    pub fn index_buffer(&self) -> Option<&Buffer> {
+        self.indices.as_ref().map(|(buffer, _)| buffer)
+    }
+
+    #[inline]
+    fn element_count(&self) -> usize {
+        if self.indices.is_some() {
+            self.index_count
+        } else {
+            self.vertex_count
+        }
+    }
+}
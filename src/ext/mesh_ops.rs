@@ -0,0 +1,713 @@
+//! Processing of indexed triangle lists on the CPU.
+//!
+//! The functions work on `MeshVertex` data with `u32` indices, as produced by
+//! `ObjModel` and the generators of `shapes`, before it is uploaded to a `Mesh`.
+//! A typical pipeline is `weld`, `smooth_normals`, `tangents`, `optimize_vertex_cache`,
+//! `optimize_overdraw` and finally `optimize_vertex_fetch`.
+
+use std::collections::HashMap;
+
+use super::{
+    vector::{add, cross, dot, length, normalize, scale, sub},
+    MeshVertex,
+};
+
+/// Recompute the normals as the angle weighted average of the adjacent faces.
+///
+/// Vertices at the same position share their normal, even if they differ in other attributes.
+/// Vertices of degenerate triangles only keep their normal.
+pub fn smooth_normals(vertices: &mut [MeshVertex], indices: &[u32]) {
+    let mut sums: HashMap<[u32; 3], [f32; 3]> = HashMap::new();
+    for triangle in indices.chunks_exact(3) {
+        let corners = corners(vertices, triangle);
+        let Some(normal) = face_normal(corners) else {
+            continue;
+        };
+        for (k, &index) in triangle.iter().enumerate() {
+            let weight = corner_angle(corners, k);
+            let sum = sums
+                .entry(position_key(&vertices[index as usize]))
+                .or_default();
+            *sum = add(*sum, scale(normal, weight));
+        }
+    }
+    for vertex in vertices {
+        if let Some(&sum) = sums.get(&position_key(vertex)) {
+            if length(sum) > 0.0 {
+                vertex.normal = normalize(sum);
+            }
+        }
+    }
+}
+
+/// Split the vertices so every triangle has its own ones carrying the face normal.
+///
+/// Returns the new vertices and indices, the triangles keep their order.
+pub fn flat_normals(vertices: &[MeshVertex], indices: &[u32]) -> (Vec<MeshVertex>, Vec<u32>) {
+    let mut flat = Vec::with_capacity(indices.len());
+    for triangle in indices.chunks_exact(3) {
+        let normal = face_normal(corners(vertices, triangle));
+        for &index in triangle {
+            let mut vertex = vertices[index as usize];
+            if let Some(normal) = normal {
+                vertex.normal = normal;
+            }
+            flat.push(vertex);
+        }
+    }
+    let indices = (0..flat.len() as u32).collect();
+    (flat, indices)
+}
+
+/// Generate tangents with the MikkTSpace algorithm, matching the tangent space
+/// normal maps are baked in by most tools.
+///
+/// Like the reference implementation with its default settings, the tangent of a vertex is
+/// the angle weighted average of the triangles which share it, are connected through edges
+/// and whose texture coordinates have the same orientation, and the sign in `w` tells whether
+/// the texture is mirrored. Vertices are identified by their position, normal and texture
+/// coordinates, and the normals should be normalized.
+///
+/// A vertex is split where its triangles need different tangents, e.g. on a mirrored seam.
+/// Returns the vertices, whose first `vertices.len()` keep their index and added ones follow,
+/// and the rewritten indices.
+pub fn tangents(vertices: &[MeshVertex], indices: &[u32]) -> (Vec<MeshVertex>, Vec<u32>) {
+    let indices = &indices[..indices.len() / 3 * 3];
+    // Every corner refers to the first vertex with the same attributes.
+    let mut shared = HashMap::new();
+    let welded: Vec<u32> = indices
+        .iter()
+        .map(|&index| {
+            *shared
+                .entry(tangent_key(&vertices[index as usize]))
+                .or_insert(index)
+        })
+        .collect();
+
+    let mut faces: Vec<TangentFace> = welded
+        .chunks_exact(3)
+        .map(|triangle| TangentFace::new(vertices, triangle))
+        .collect();
+    let good: Vec<usize> = (0..faces.len()).filter(|&f| !faces[f].degenerate).collect();
+
+    // Triangles sharing an edge in opposite directions are neighbors.
+    let mut edges: HashMap<(u32, u32), Vec<(usize, usize)>> = HashMap::new();
+    for &f in &good {
+        for k in 0..3 {
+            let edge = (welded[f * 3 + k], welded[f * 3 + (k + 1) % 3]);
+            edges.entry(edge).or_default().push((f, k));
+        }
+    }
+    for &f in &good {
+        for k in 0..3 {
+            if faces[f].neighbors[k].is_some() {
+                continue;
+            }
+            let (a, b) = (welded[f * 3 + k], welded[f * 3 + (k + 1) % 3]);
+            let candidates = edges.get(&(b, a)).map_or(&[][..], Vec::as_slice);
+            if let Some(&(g, j)) = candidates
+                .iter()
+                .find(|&&(g, j)| g != f && faces[g].neighbors[j].is_none())
+            {
+                faces[f].neighbors[k] = Some(g);
+                faces[g].neighbors[j] = Some(f);
+            }
+        }
+    }
+
+    // Group the triangles around every vertex by connectivity and orientation,
+    // triangles with degenerate tangents only join the groups of their neighbors.
+    let mut groups: Vec<TangentGroup> = Vec::new();
+    for &f in &good {
+        for k in 0..3 {
+            if faces[f].any || faces[f].groups[k].is_some() {
+                continue;
+            }
+            let group = groups.len();
+            groups.push(TangentGroup {
+                vertex: welded[f * 3 + k],
+                orient: faces[f].orient,
+                faces: vec![f],
+            });
+            faces[f].groups[k] = Some(group);
+            let neighbors = [faces[f].neighbors[k], faces[f].neighbors[(k + 2) % 3]];
+            for neighbor in neighbors.into_iter().flatten() {
+                assign_group(&mut faces, &welded, &mut groups, neighbor, group);
+            }
+        }
+    }
+
+    // Corners without a group fall back to this.
+    let mut corners = vec![([1.0, 0.0, 0.0], false); welded.len()];
+    for (index, group) in groups.iter().enumerate() {
+        let normal = vertices[group.vertex as usize].normal;
+        let mut evaluated: Vec<(Vec<usize>, [f32; 3])> = Vec::new();
+        for &f in &group.faces {
+            let k = (0..3)
+                .find(|&k| faces[f].groups[k] == Some(index))
+                .expect("the triangle belongs to the group");
+            let (os, ot) = faces[f].projected(normal);
+            // With the default angular threshold of 180 degrees
+            // only opposite tangents are not averaged.
+            let mut members: Vec<usize> = group
+                .faces
+                .iter()
+                .copied()
+                .filter(|&t| {
+                    let (os2, ot2) = faces[t].projected(normal);
+                    faces[f].any
+                        || faces[t].any
+                        || f == t
+                        || (dot(os, os2) > -1.0 && dot(ot, ot2) > -1.0)
+                })
+                .collect();
+            members.sort_unstable();
+            let tangent = match evaluated.iter().find(|(other, _)| *other == members) {
+                Some(&(_, tangent)) => tangent,
+                None => {
+                    let tangent = eval_tangent(vertices, &welded, &faces, &members, group.vertex);
+                    evaluated.push((members, tangent));
+                    tangent
+                }
+            };
+            corners[f * 3 + k] = (tangent, group.orient);
+        }
+    }
+    // Corners of degenerate triangles copy the first corner of the same vertex.
+    let mut first = HashMap::new();
+    for &f in &good {
+        for k in 0..3 {
+            first.entry(welded[f * 3 + k]).or_insert(f * 3 + k);
+        }
+    }
+    for f in (0..faces.len()).filter(|&f| faces[f].degenerate) {
+        for k in 0..3 {
+            if let Some(&corner) = first.get(&welded[f * 3 + k]) {
+                corners[f * 3 + k] = corners[corner];
+            }
+        }
+    }
+
+    let mut output = vertices.to_vec();
+    let mut assigned = vec![false; vertices.len()];
+    let mut split = HashMap::new();
+    let indices = indices
+        .iter()
+        .zip(corners)
+        .map(|(&index, (tangent, orient))| {
+            let sign = if orient { 1.0 } else { -1.0 };
+            let tangent = [tangent[0], tangent[1], tangent[2], sign];
+            let vertex = &mut output[index as usize];
+            if !assigned[index as usize] {
+                assigned[index as usize] = true;
+                vertex.tangent = tangent;
+                return index;
+            }
+            if vertex.tangent == tangent {
+                return index;
+            }
+            let key = (index, tangent.map(f32::to_bits));
+            *split.entry(key).or_insert_with(|| {
+                let vertex = MeshVertex {
+                    tangent,
+                    ..vertices[index as usize]
+                };
+                output.push(vertex);
+                (output.len() - 1) as u32
+            })
+        })
+        .collect();
+    (output, indices)
+}
+
+/// Triangle of `tangents(...)`.
+struct TangentFace {
+    /// Unit tangent and bitangent of the triangle, zero if the texture coordinates are degenerate.
+    os: [f32; 3],
+    ot: [f32; 3],
+    /// Whether the texture coordinates are counter-clockwise.
+    orient: bool,
+    /// Whether the tangents are degenerate, so the triangle joins any group.
+    any: bool,
+    /// Whether two corners are at the same position.
+    degenerate: bool,
+    /// Triangle across the edge from corner `k` to corner `k + 1`.
+    neighbors: [Option<usize>; 3],
+    /// Group of each corner.
+    groups: [Option<usize>; 3],
+}
+
+impl TangentFace {
+    fn new(vertices: &[MeshVertex], triangle: &[u32]) -> Self {
+        let [a, b, c] = [0, 1, 2].map(|k| &vertices[triangle[k] as usize]);
+        let (d1, d2) = (sub(b.position, a.position), sub(c.position, a.position));
+        let (t21x, t21y) = (b.uv[0] - a.uv[0], b.uv[1] - a.uv[1]);
+        let (t31x, t31y) = (c.uv[0] - a.uv[0], c.uv[1] - a.uv[1]);
+        let area = t21x * t31y - t21y * t31x;
+        let mut face = Self {
+            os: [0.0; 3],
+            ot: [0.0; 3],
+            orient: area > 0.0,
+            any: true,
+            degenerate: a.position == b.position
+                || a.position == c.position
+                || b.position == c.position,
+            neighbors: [None; 3],
+            groups: [None; 3],
+        };
+        if not_zero(area) {
+            let os = sub(scale(d1, t31y), scale(d2, t21y));
+            let ot = add(scale(d1, -t31x), scale(d2, t21x));
+            let sign = if face.orient { 1.0 } else { -1.0 };
+            let (len_os, len_ot) = (length(os), length(ot));
+            if not_zero(len_os) {
+                face.os = scale(os, sign / len_os);
+            }
+            if not_zero(len_ot) {
+                face.ot = scale(ot, sign / len_ot);
+            }
+            face.any = !(not_zero(len_os / area.abs()) && not_zero(len_ot / area.abs()));
+        }
+        face
+    }
+
+    /// Return the tangent and bitangent projected onto the plane of `normal`.
+    #[inline]
+    fn projected(&self, normal: [f32; 3]) -> ([f32; 3], [f32; 3]) {
+        let os = normalize_safe(sub(self.os, scale(normal, dot(normal, self.os))));
+        let ot = normalize_safe(sub(self.ot, scale(normal, dot(normal, self.ot))));
+        (os, ot)
+    }
+}
+
+/// Triangles around one vertex sharing a tangent space.
+struct TangentGroup {
+    vertex: u32,
+    orient: bool,
+    faces: Vec<usize>,
+}
+
+/// Add the triangle `f` and its neighbors around the vertex of `group` to `group`,
+/// as long as their orientation matches.
+fn assign_group(
+    faces: &mut [TangentFace],
+    welded: &[u32],
+    groups: &mut [TangentGroup],
+    f: usize,
+    group: usize,
+) {
+    let vertex = groups[group].vertex;
+    let Some(k) = (0..3).find(|&k| welded[f * 3 + k] == vertex) else {
+        return;
+    };
+    if faces[f].groups[k].is_some() {
+        return;
+    }
+    // The first group reaching a triangle with degenerate tangents decides its orientation.
+    if faces[f].any && faces[f].groups.iter().all(Option::is_none) {
+        faces[f].orient = groups[group].orient;
+    }
+    if faces[f].orient != groups[group].orient {
+        return;
+    }
+    groups[group].faces.push(f);
+    faces[f].groups[k] = Some(group);
+    let neighbors = [faces[f].neighbors[k], faces[f].neighbors[(k + 2) % 3]];
+    for neighbor in neighbors.into_iter().flatten() {
+        assign_group(faces, welded, groups, neighbor, group);
+    }
+}
+
+/// Return the tangent at `vertex` averaged over the triangles `members`,
+/// weighted by the angle of their corner at `vertex`.
+fn eval_tangent(
+    vertices: &[MeshVertex],
+    welded: &[u32],
+    faces: &[TangentFace],
+    members: &[usize],
+    vertex: u32,
+) -> [f32; 3] {
+    let mut sum = [0.0; 3];
+    for &f in members.iter().filter(|&&f| !faces[f].any) {
+        let Some(k) = (0..3).find(|&k| welded[f * 3 + k] == vertex) else {
+            continue;
+        };
+        let normal = vertices[vertex as usize].normal;
+        let (os, _) = faces[f].projected(normal);
+        let position = |k: usize| vertices[welded[f * 3 + k] as usize].position;
+        let project = |v: [f32; 3]| normalize_safe(sub(v, scale(normal, dot(normal, v))));
+        let v1 = project(sub(position((k + 2) % 3), position(k)));
+        let v2 = project(sub(position((k + 1) % 3), position(k)));
+        let angle = dot(v1, v2).clamp(-1.0, 1.0).acos();
+        sum = add(sum, scale(os, angle));
+    }
+    normalize_safe(sum)
+}
+
+/// Whether `x` is not zero, as tested by MikkTSpace.
+#[inline]
+fn not_zero(x: f32) -> bool {
+    x.abs() > f32::MIN_POSITIVE
+}
+
+/// Return `v` normalized, or unchanged if it is zero.
+#[inline]
+fn normalize_safe(v: [f32; 3]) -> [f32; 3] {
+    if length(v) != 0.0 {
+        normalize(v)
+    } else {
+        v
+    }
+}
+
+/// Return the bits of the attributes used by `tangents(...)`, with `-0.0` equal to `0.0`.
+#[inline]
+fn tangent_key(vertex: &MeshVertex) -> [u32; 8] {
+    let bits = |x: f32| (x + 0.0).to_bits();
+    let [x, y, z] = vertex.position.map(bits);
+    let [nx, ny, nz] = vertex.normal.map(bits);
+    let [u, v] = vertex.uv.map(bits);
+    [x, y, z, nx, ny, nz, u, v]
+}
+
+/// Merge vertices whose attributes all round to the same multiples of `epsilon`,
+/// or which are bitwise equal if `epsilon` is 0.
+///
+/// Returns the merged vertices in order of first appearance and the rewritten indices.
+pub fn weld(vertices: &[MeshVertex], indices: &[u32], epsilon: f32) -> (Vec<MeshVertex>, Vec<u32>) {
+    let quantize = |value: f32| {
+        if epsilon > 0.0 {
+            (value / epsilon).round() as i64
+        } else {
+            value.to_bits() as i64
+        }
+    };
+    let mut unique = HashMap::new();
+    let mut welded = Vec::new();
+    let remap: Vec<u32> = vertices
+        .iter()
+        .map(|vertex| {
+            let attributes = vertex
+                .position
+                .iter()
+                .chain(&vertex.normal)
+                .chain(&vertex.uv)
+                .chain(&vertex.tangent);
+            let key: Vec<i64> = attributes.map(|&value| quantize(value)).collect();
+            *unique.entry(key).or_insert_with(|| {
+                welded.push(*vertex);
+                welded.len() as u32 - 1
+            })
+        })
+        .collect();
+    let indices = indices.iter().map(|&index| remap[index as usize]).collect();
+    (welded, indices)
+}
+
+/// Return the average number of vertices transformed per triangle
+/// by a first-in first-out cache of `cache_size` entries.
+///
+/// It lies between about 0.5 for a perfect order and 3 when nothing is reused.
+pub fn average_cache_miss_ratio(indices: &[u32], vertex_count: usize, cache_size: usize) -> f32 {
+    let triangles = indices.len() / 3;
+    if triangles == 0 {
+        return 0.0;
+    }
+    let mut cache = FifoCache::new(vertex_count, cache_size);
+    let misses: usize = indices
+        .iter()
+        .map(|&index| cache.access(index) as usize)
+        .sum();
+    misses as f32 / triangles as f32
+}
+
+/// Reorder the triangles to reuse transformed vertices,
+/// by Tom Forsyth's linear-speed vertex cache optimisation.
+///
+/// The winding of every triangle is kept.
+pub fn optimize_vertex_cache(indices: &mut [u32], vertex_count: usize) {
+    const CACHE_SIZE: usize = 32;
+    let triangle_count = indices.len() / 3;
+    if triangle_count == 0 {
+        return;
+    }
+
+    // Triangles adjacent to each vertex, in one array with the ranges in `offsets`.
+    let mut valence = vec![0u32; vertex_count];
+    for &index in &indices[..triangle_count * 3] {
+        valence[index as usize] += 1;
+    }
+    let mut offsets = vec![0usize; vertex_count + 1];
+    for vertex in 0..vertex_count {
+        offsets[vertex + 1] = offsets[vertex] + valence[vertex] as usize;
+    }
+    let mut adjacency = vec![0usize; offsets[vertex_count]];
+    let mut filled = offsets.clone();
+    for (triangle, chunk) in indices.chunks_exact(3).enumerate() {
+        for &index in chunk {
+            adjacency[filled[index as usize]] = triangle;
+            filled[index as usize] += 1;
+        }
+    }
+
+    let mut remaining = valence;
+    let mut cache_position: Vec<Option<usize>> = vec![None; vertex_count];
+    let mut vertex_scores: Vec<f32> = (0..vertex_count)
+        .map(|vertex| forsyth_score(None, remaining[vertex]))
+        .collect();
+    let triangle_score = |chunk: &[u32], scores: &[f32]| -> f32 {
+        chunk.iter().map(|&index| scores[index as usize]).sum()
+    };
+    let mut emitted = vec![false; triangle_count];
+    let mut order = Vec::with_capacity(triangle_count);
+    let mut cache: Vec<u32> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut best = Some(0);
+    let mut cursor = 0;
+
+    while order.len() < triangle_count {
+        let triangle = match best {
+            Some(triangle) => triangle,
+            None => {
+                // Nothing in the cache is adjacent to a remaining triangle, take the next one.
+                while emitted[cursor] {
+                    cursor += 1;
+                }
+                cursor
+            }
+        };
+        emitted[triangle] = true;
+        order.push(triangle);
+
+        let chunk = &indices[triangle * 3..triangle * 3 + 3];
+        let mut next_cache: Vec<u32> = chunk.to_vec();
+        for &index in chunk {
+            remaining[index as usize] -= 1;
+        }
+        next_cache.extend(cache.iter().filter(|index| !chunk.contains(index)));
+        let evicted: Vec<u32> = next_cache.split_off(next_cache.len().min(CACHE_SIZE));
+        for &index in &evicted {
+            cache_position[index as usize] = None;
+        }
+        for (position, &index) in next_cache.iter().enumerate() {
+            cache_position[index as usize] = Some(position);
+        }
+        cache = next_cache;
+
+        best = None;
+        let mut best_score = f32::NEG_INFINITY;
+        for &index in cache.iter().chain(&evicted) {
+            let vertex = index as usize;
+            vertex_scores[vertex] = forsyth_score(cache_position[vertex], remaining[vertex]);
+        }
+        for &index in cache.iter().chain(&evicted) {
+            let vertex = index as usize;
+            for &adjacent in &adjacency[offsets[vertex]..offsets[vertex + 1]] {
+                if emitted[adjacent] {
+                    continue;
+                }
+                let score =
+                    triangle_score(&indices[adjacent * 3..adjacent * 3 + 3], &vertex_scores);
+                if score > best_score {
+                    best_score = score;
+                    best = Some(adjacent);
+                }
+            }
+        }
+    }
+
+    let reordered: Vec<u32> = order
+        .iter()
+        .flat_map(|&triangle| indices[triangle * 3..triangle * 3 + 3].to_vec())
+        .collect();
+    indices[..reordered.len()].copy_from_slice(&reordered);
+}
+
+/// Reorder clusters of triangles so the ones facing outwards come first,
+/// which lets the depth test reject more of the hidden fragments.
+///
+/// It should run after `optimize_vertex_cache(...)`, whose order is kept inside the clusters.
+/// `threshold` is the allowed growth of the average cache miss ratio, e.g. 1.05.
+pub fn optimize_overdraw(indices: &mut [u32], vertices: &[MeshVertex], threshold: f32) {
+    const CACHE_SIZE: usize = 16;
+    let triangle_count = indices.len() / 3;
+    if triangle_count == 0 {
+        return;
+    }
+    let limit = average_cache_miss_ratio(indices, vertices.len(), CACHE_SIZE) * threshold;
+
+    // Hard boundaries start where a triangle misses the cache completely,
+    // soft ones split a cluster once it reuses the cache well enough.
+    let mut cache = FifoCache::new(vertices.len(), CACHE_SIZE);
+    let mut boundaries = vec![0];
+    let mut start = 0;
+    let mut misses = 0;
+    for (triangle, chunk) in indices.chunks_exact(3).enumerate() {
+        let triangle_misses: usize = chunk
+            .iter()
+            .map(|&index| cache.access(index) as usize)
+            .sum();
+        if triangle > start && triangle_misses == 3 {
+            boundaries.push(triangle);
+            start = triangle;
+            misses = 0;
+        }
+        misses += triangle_misses;
+        if misses as f32 / (triangle - start + 1) as f32 <= limit && triangle + 1 < triangle_count {
+            boundaries.push(triangle + 1);
+            start = triangle + 1;
+            misses = 0;
+            cache.clear();
+        }
+    }
+    boundaries.push(triangle_count);
+    boundaries.dedup();
+
+    // Sort the clusters by how much they face away from the center of the mesh.
+    let centroid = |chunk: &[u32]| {
+        let sum = chunk.iter().fold([0.0; 3], |sum, &index| {
+            add(sum, vertices[index as usize].position)
+        });
+        scale(sum, 1.0 / 3.0)
+    };
+    let (mut center, mut total_area) = ([0.0; 3], 0.0);
+    for chunk in indices.chunks_exact(3) {
+        let area = length(face_cross(corners(vertices, chunk)));
+        center = add(center, scale(centroid(chunk), area));
+        total_area += area;
+    }
+    if total_area > 0.0 {
+        center = scale(center, 1.0 / total_area);
+    }
+    let mut clusters: Vec<(f32, std::ops::Range<usize>)> = boundaries
+        .windows(2)
+        .map(|range| {
+            let (mut position, mut normal, mut area) = ([0.0; 3], [0.0; 3], 0.0);
+            for chunk in indices[range[0] * 3..range[1] * 3].chunks_exact(3) {
+                let face = face_cross(corners(vertices, chunk));
+                let face_area = length(face);
+                position = add(position, scale(centroid(chunk), face_area));
+                normal = add(normal, face);
+                area += face_area;
+            }
+            let key = if area > 0.0 && length(normal) > 0.0 {
+                dot(sub(scale(position, 1.0 / area), center), normalize(normal))
+            } else {
+                0.0
+            };
+            (key, range[0]..range[1])
+        })
+        .collect();
+    clusters.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let reordered: Vec<u32> = clusters
+        .iter()
+        .flat_map(|(_, range)| indices[range.start * 3..range.end * 3].to_vec())
+        .collect();
+    indices[..reordered.len()].copy_from_slice(&reordered);
+}
+
+/// Reorder the vertices by their first use in `indices` and drop the unused ones,
+/// so the vertex fetch reads memory mostly in order.
+///
+/// Returns the new vertices and rewrites `indices` to them.
+pub fn optimize_vertex_fetch<V: Copy>(vertices: &[V], indices: &mut [u32]) -> Vec<V> {
+    let mut remap = vec![u32::MAX; vertices.len()];
+    let mut fetched = Vec::with_capacity(vertices.len());
+    for index in indices.iter_mut() {
+        let slot = &mut remap[*index as usize];
+        if *slot == u32::MAX {
+            *slot = fetched.len() as u32;
+            fetched.push(vertices[*index as usize]);
+        }
+        *index = *slot;
+    }
+    fetched
+}
+
+/// Cache which evicts the oldest vertex, as used to model the post-transform cache.
+struct FifoCache {
+    timestamps: Vec<usize>,
+    time: usize,
+    size: usize,
+}
+
+impl FifoCache {
+    fn new(vertex_count: usize, size: usize) -> Self {
+        Self {
+            timestamps: vec![0; vertex_count],
+            time: size + 1,
+            size,
+        }
+    }
+
+    /// Access `index`, returning `true` for a miss.
+    #[inline]
+    fn access(&mut self, index: u32) -> bool {
+        let timestamp = &mut self.timestamps[index as usize];
+        if self.time - *timestamp > self.size {
+            *timestamp = self.time;
+            self.time += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.time += self.size + 1;
+    }
+}
+
+/// Score of a vertex in Forsyth's algorithm, higher for vertices worth emitting soon.
+fn forsyth_score(cache_position: Option<usize>, remaining: u32) -> f32 {
+    const CACHE_SIZE: usize = 32;
+    if remaining == 0 {
+        return -1.0;
+    }
+    let cache_score = match cache_position {
+        None => 0.0,
+        // The vertices of the last triangle get a fixed score so it is not simply repeated.
+        Some(position) if position < 3 => 0.75,
+        Some(position) => {
+            let scaled = (position - 3) as f32 / (CACHE_SIZE - 3) as f32;
+            (1.0 - scaled).powf(1.5)
+        }
+    };
+    // Vertices with few remaining triangles are boosted to finish them off.
+    cache_score + 2.0 * (remaining as f32).powf(-0.5)
+}
+
+#[inline]
+fn corners(vertices: &[MeshVertex], triangle: &[u32]) -> [[f32; 3]; 3] {
+    [0, 1, 2].map(|k| vertices[triangle[k] as usize].position)
+}
+
+#[inline]
+fn face_cross([a, b, c]: [[f32; 3]; 3]) -> [f32; 3] {
+    cross(sub(b, a), sub(c, a))
+}
+
+#[inline]
+fn face_normal(corners: [[f32; 3]; 3]) -> Option<[f32; 3]> {
+    let normal = face_cross(corners);
+    (length(normal) > 0.0).then(|| normalize(normal))
+}
+
+/// Return the angle of the triangle at its corner `k`.
+#[inline]
+fn corner_angle(corners: [[f32; 3]; 3], k: usize) -> f32 {
+    let corner = corners[k];
+    let a = sub(corners[(k + 1) % 3], corner);
+    let b = sub(corners[(k + 2) % 3], corner);
+    let (la, lb) = (length(a), length(b));
+    if la == 0.0 || lb == 0.0 {
+        return 0.0;
+    }
+    (dot(a, b) / (la * lb)).clamp(-1.0, 1.0).acos()
+}
+
+#[inline]
+fn position_key(vertex: &MeshVertex) -> [u32; 3] {
+    vertex.position.map(f32::to_bits)
+}
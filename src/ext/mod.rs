@@ -1,6 +1,40 @@
 //! Here contains the basic encapsulation of OpenGL content,
 //! which are not APIs directly exposed by OpenGL.
 
+mod buffer_allocator;
+mod diagnostics;
+#[cfg(feature = "gltf")]
+mod gltf;
+mod hot_program;
+mod mesh;
+pub mod mesh_ops;
+mod obj;
+mod preprocessor;
+mod program_builder;
+mod program_cache;
+mod shader_library;
+pub mod shapes;
+mod stream_buffer;
+mod uniform_buffer;
+mod vector;
 mod vertex;
+mod vertex_inputs;
 
+#[cfg(feature = "gltf")]
+pub use self::gltf::{
+    AlphaMode, GltfLocations, GltfMesh, GltfNode, GltfPrimitive, GltfScene, GltfSceneInfo,
+    PbrMaterial, TextureRef,
+};
+pub use buffer_allocator::{BufferAllocator, BufferSlice, RangeAllocator};
+pub use diagnostics::{Diagnostic, Severity};
+pub use hot_program::{HotProgram, HotSources, WatchedFiles};
+pub use mesh::{Aabb, Indices, Mesh, MeshVertex, SubMesh};
+pub use obj::{Material, ObjGroup, ObjModel};
+pub use preprocessor::{DirectoryProvider, FileProvider, Preprocessed, Preprocessor};
+pub use program_builder::{ProgramBuilder, ProgramError};
+pub use program_cache::ProgramCache;
+pub use shader_library::ShaderLibrary;
+pub use stream_buffer::StreamBuffer;
+pub use uniform_buffer::UniformBuffer;
 pub use vertex::Vertex;
+pub use vertex_inputs::{LayoutError, VertexInputs};
@@ -0,0 +1,361 @@
+use std::{collections::HashMap, path::Path, str::SplitWhitespace};
+
+use crate::{Context, Mode};
+
+use super::{Aabb, Indices, Mesh, MeshVertex, SubMesh};
+
+/// Material described by a Wavefront MTL file.
+///
+/// Texture maps are kept as written in the file, relative to the MTL file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material {
+    pub name: String,
+    /// `Ka`
+    pub ambient: [f32; 3],
+    /// `Kd`
+    pub diffuse: [f32; 3],
+    /// `Ks`
+    pub specular: [f32; 3],
+    /// `Ke`
+    pub emissive: [f32; 3],
+    /// `Ns`
+    pub shininess: f32,
+    /// `d`, or `1 - Tr`
+    pub opacity: f32,
+    /// `Ni`
+    pub optical_density: f32,
+    /// `illum`
+    pub illumination: u32,
+    /// `map_Ka`
+    pub ambient_map: Option<String>,
+    /// `map_Kd`
+    pub diffuse_map: Option<String>,
+    /// `map_Ks`
+    pub specular_map: Option<String>,
+    /// `map_Ns`
+    pub shininess_map: Option<String>,
+    /// `map_d`
+    pub opacity_map: Option<String>,
+    /// `map_Bump`, `bump` or `norm`
+    pub normal_map: Option<String>,
+}
+
+impl Material {
+    /// Create a material with the default values of the MTL format.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ambient: [0.0; 3],
+            diffuse: [0.8; 3],
+            specular: [0.0; 3],
+            emissive: [0.0; 3],
+            shininess: 0.0,
+            opacity: 1.0,
+            optical_density: 1.0,
+            illumination: 2,
+            ambient_map: None,
+            diffuse_map: None,
+            specular_map: None,
+            shininess_map: None,
+            opacity_map: None,
+            normal_map: None,
+        }
+    }
+
+    /// Parse all materials of a MTL file.
+    pub fn parse_mtl(source: &str) -> Result<Vec<Self>, String> {
+        let mut materials: Vec<Self> = Vec::new();
+        for (number, line) in lines(source) {
+            let mut tokens = line.split_whitespace();
+            let Some(keyword) = tokens.next() else {
+                continue;
+            };
+            if keyword == "newmtl" {
+                let name = rest(&line, keyword);
+                if name.is_empty() {
+                    return Err(format!("Line {}: missing material name", number));
+                }
+                materials.push(Self::new(name));
+                continue;
+            }
+            let Some(material) = materials.last_mut() else {
+                return Err(format!("Line {}: '{}' before 'newmtl'", number, keyword));
+            };
+            let error = |what: &str| format!("Line {}: invalid {}", number, what);
+            match keyword {
+                "Ka" => material.ambient = floats(&mut tokens).ok_or_else(|| error("Ka"))?,
+                "Kd" => material.diffuse = floats(&mut tokens).ok_or_else(|| error("Kd"))?,
+                "Ks" => material.specular = floats(&mut tokens).ok_or_else(|| error("Ks"))?,
+                "Ke" => material.emissive = floats(&mut tokens).ok_or_else(|| error("Ke"))?,
+                "Ns" => material.shininess = float(&mut tokens).ok_or_else(|| error("Ns"))?,
+                "d" => material.opacity = float(&mut tokens).ok_or_else(|| error("d"))?,
+                "Tr" => material.opacity = 1.0 - float(&mut tokens).ok_or_else(|| error("Tr"))?,
+                "Ni" => material.optical_density = float(&mut tokens).ok_or_else(|| error("Ni"))?,
+                "illum" => {
+                    material.illumination = tokens
+                        .next()
+                        .and_then(|token| token.parse().ok())
+                        .ok_or_else(|| error("illum"))?
+                }
+                "map_Ka" => material.ambient_map = Some(map(&line, keyword)),
+                "map_Kd" => material.diffuse_map = Some(map(&line, keyword)),
+                "map_Ks" => material.specular_map = Some(map(&line, keyword)),
+                "map_Ns" => material.shininess_map = Some(map(&line, keyword)),
+                "map_d" => material.opacity_map = Some(map(&line, keyword)),
+                "map_Bump" | "map_bump" | "bump" | "norm" => {
+                    material.normal_map = Some(map(&line, keyword))
+                }
+                _ => {}
+            }
+        }
+        Ok(materials)
+    }
+}
+
+/// Part of an OBJ model drawn with one material.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ObjGroup {
+    /// Name given by `usemtl`, `None` for faces before any `usemtl`.
+    pub material: Option<String>,
+    /// Range in the indices of the model.
+    pub range: SubMesh,
+}
+
+/// Triangulated model read from a Wavefront OBJ file.
+///
+/// Each distinct combination of position, texture coordinates and normal becomes one vertex.
+/// The indices are ordered by material, so every material is drawn by one range.
+/// Missing normals and texture coordinates are left zero, as are the tangents.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ObjModel {
+    pub vertices: Vec<MeshVertex>,
+    pub indices: Vec<u32>,
+    pub groups: Vec<ObjGroup>,
+    pub materials: Vec<Material>,
+    /// Files named by `mtllib`, relative to the OBJ file.
+    pub material_libs: Vec<String>,
+}
+
+impl ObjModel {
+    /// Parse an OBJ file, materials are not loaded.
+    ///
+    /// Polygons are triangulated as fans, points and lines are ignored.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut positions = Vec::new();
+        let mut uvs = Vec::new();
+        let mut normals = Vec::new();
+        let mut model = Self::default();
+        let mut unique = HashMap::new();
+        let mut current = 0;
+        let mut groups: Vec<(Option<String>, Vec<u32>)> = vec![(None, Vec::new())];
+        let mut polygon = Vec::new();
+
+        for (number, line) in lines(source) {
+            let mut tokens = line.split_whitespace();
+            let Some(keyword) = tokens.next() else {
+                continue;
+            };
+            let error = |what: &str| format!("Line {}: invalid {}", number, what);
+            match keyword {
+                "v" => positions.push(floats::<3>(&mut tokens).ok_or_else(|| error("position"))?),
+                "vn" => normals.push(floats::<3>(&mut tokens).ok_or_else(|| error("normal"))?),
+                "vt" => {
+                    let u = float(&mut tokens).ok_or_else(|| error("texture coordinates"))?;
+                    let v = float(&mut tokens).unwrap_or(0.0);
+                    uvs.push([u, v]);
+                }
+                "usemtl" => {
+                    let name = Some(rest(&line, keyword).to_string());
+                    current = match groups.iter().position(|(material, _)| *material == name) {
+                        Some(index) => index,
+                        None => {
+                            groups.push((name, Vec::new()));
+                            groups.len() - 1
+                        }
+                    };
+                }
+                "mtllib" => model.material_libs.extend(tokens.map(str::to_string)),
+                "f" => {
+                    polygon.clear();
+                    for token in tokens {
+                        let key = face_vertex(token, positions.len(), uvs.len(), normals.len())
+                            .ok_or_else(|| error(&format!("face vertex '{}'", token)))?;
+                        let index = *unique.entry(key).or_insert_with(|| {
+                            let (position, uv, normal) = key;
+                            model.vertices.push(MeshVertex {
+                                position: positions[position],
+                                normal: normal.map_or([0.0; 3], |normal| normals[normal]),
+                                uv: uv.map_or([0.0; 2], |uv| uvs[uv]),
+                                tangent: [0.0; 4],
+                            });
+                            (model.vertices.len() - 1) as u32
+                        });
+                        polygon.push(index);
+                    }
+                    if polygon.len() < 3 {
+                        return Err(error("face with less than 3 vertices"));
+                    }
+                    let indices = &mut groups[current].1;
+                    for i in 1..polygon.len() - 1 {
+                        indices.extend([polygon[0], polygon[i], polygon[i + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (material, indices) in groups {
+            if indices.is_empty() {
+                continue;
+            }
+            let first = model.indices.len();
+            model.indices.extend(indices);
+            model.groups.push(ObjGroup {
+                material,
+                range: SubMesh::from(first..model.indices.len()),
+            });
+        }
+        Ok(model)
+    }
+
+    /// Read and parse the OBJ file at `path` together with its MTL files.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+        let mut model = Self::parse(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let directory = path.parent().unwrap_or(Path::new(""));
+        for lib in &model.material_libs {
+            let path = directory.join(lib);
+            let source = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+            let materials =
+                Material::parse_mtl(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+            model.materials.extend(materials);
+        }
+        Ok(model)
+    }
+
+    /// Return the material of `group`, if it is known.
+    #[inline]
+    pub fn material(&self, group: &ObjGroup) -> Option<&Material> {
+        let name = group.material.as_ref()?;
+        self.materials
+            .iter()
+            .find(|material| material.name == *name)
+    }
+
+    /// Return the bounding box of all vertices.
+    #[inline]
+    pub fn bounds(&self) -> Aabb {
+        Aabb::from_points(self.vertices.iter().map(|vertex| vertex.position))
+    }
+
+    /// Upload the model into a mesh of triangles with one sub-mesh per group.
+    ///
+    /// The groups keep their order, so sub-mesh `i` is `groups[i]`.
+    pub fn to_mesh(&self, context: &Context) -> Mesh {
+        let mut mesh = if self.vertices.len() <= u16::MAX as usize + 1 {
+            let indices: Vec<u16> = self.indices.iter().map(|&index| index as u16).collect();
+            Mesh::new(
+                context,
+                &self.vertices,
+                Some(Indices::U16(&indices)),
+                Mode::Triangles,
+            )
+        } else {
+            let indices = Some(Indices::U32(&self.indices));
+            Mesh::new(context, &self.vertices, indices, Mode::Triangles)
+        };
+        for group in &self.groups {
+            mesh.add_sub_mesh(group.range);
+        }
+        mesh
+    }
+}
+
+type FaceVertex = (usize, Option<usize>, Option<usize>);
+
+/// Parse `v`, `v/vt`, `v//vn` or `v/vt/vn`, resolving negative indices.
+fn face_vertex(token: &str, positions: usize, uvs: usize, normals: usize) -> Option<FaceVertex> {
+    let mut parts = token.split('/');
+    let position = resolve(parts.next()?, positions)?;
+    let uv = match parts.next() {
+        None | Some("") => None,
+        Some(part) => Some(resolve(part, uvs)?),
+    };
+    let normal = match parts.next() {
+        None | Some("") => None,
+        Some(part) => Some(resolve(part, normals)?),
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((position, uv, normal))
+}
+
+/// Convert a 1-based or negative relative index to a 0-based one.
+fn resolve(index: &str, len: usize) -> Option<usize> {
+    let index: isize = index.parse().ok()?;
+    let index = match index {
+        0 => return None,
+        1.. => index as usize - 1,
+        _ => len.checked_sub(index.unsigned_abs())?,
+    };
+    (index < len).then_some(index)
+}
+
+/// Iterate over the numbered lines without comments, joining lines ending with `\`.
+fn lines(source: &str) -> impl Iterator<Item = (usize, String)> + '_ {
+    let mut lines = source.lines().enumerate();
+    std::iter::from_fn(move || {
+        let (index, line) = lines.next()?;
+        let mut line = line.to_string();
+        while line.ends_with('\\') {
+            line.pop();
+            match lines.next() {
+                Some((_, next)) => line.push_str(next),
+                None => break,
+            }
+        }
+        if let Some(comment) = line.find('#') {
+            line.truncate(comment);
+        }
+        Some((index + 1, line))
+    })
+}
+
+/// Return the trimmed text after `keyword`.
+#[inline]
+fn rest<'a>(line: &'a str, keyword: &str) -> &'a str {
+    line.trim_start()[keyword.len()..].trim()
+}
+
+/// Return the file name of a texture map statement, dropping its options.
+///
+/// Without options the whole rest is used, so names may contain spaces.
+fn map(line: &str, keyword: &str) -> String {
+    let rest = rest(line, keyword);
+    if rest.starts_with('-') {
+        rest.split_whitespace()
+            .last()
+            .unwrap_or_default()
+            .to_string()
+    } else {
+        rest.to_string()
+    }
+}
+
+#[inline]
+fn float(tokens: &mut SplitWhitespace) -> Option<f32> {
+    tokens.next()?.parse().ok()
+}
+
+#[inline]
+fn floats<const N: usize>(tokens: &mut SplitWhitespace) -> Option<[f32; N]> {
+    let mut values = [0.0; N];
+    for value in &mut values {
+        *value = float(tokens)?;
+    }
+    Some(values)
+}
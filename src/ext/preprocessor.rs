@@ -0,0 +1,265 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Component, Path, PathBuf},
+};
+
+use super::Diagnostic;
+
+/// Source of the files resolved by `#include`.
+pub trait FileProvider {
+    /// Return the content of the file at `path`, which is relative to the root
+    /// of the provider and uses `/` as separator.
+    fn read(&self, path: &str) -> Result<String, String>;
+}
+
+/// In-memory files, keyed by their path.
+impl FileProvider for HashMap<String, String> {
+    #[inline]
+    fn read(&self, path: &str) -> Result<String, String> {
+        self.get(path)
+            .cloned()
+            .ok_or_else(|| format!("File '{}' not found", path))
+    }
+}
+
+/// Files read from a directory on disk.
+///
+/// Paths which leave the directory, by `..` or by being absolute, are rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DirectoryProvider {
+    root: PathBuf,
+}
+
+impl DirectoryProvider {
+    #[inline]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl FileProvider for DirectoryProvider {
+    fn read(&self, path: &str) -> Result<String, String> {
+        let inside = Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if !inside {
+            return Err(format!("Path '{}' is outside of the root", path));
+        }
+        let full = self.root.join(path);
+        std::fs::read_to_string(&full)
+            .map_err(|err| format!("Failed to read '{}': {}", full.display(), err))
+    }
+}
+
+/// Output of `Preprocessor::process(...)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Preprocessed {
+    /// The code with every include expanded, ready for `Shader::source(...)`.
+    pub code: String,
+    /// Paths of the files, indexed by the source string number of the `#line` directives.
+    /// The first one is the file which was processed.
+    pub files: Vec<String>,
+    sources: Vec<String>,
+    /// File index and line of every line of `code`.
+    lines: Vec<(u32, u32)>,
+}
+
+impl Preprocessed {
+    /// Return the content of every file, indexed like `files`.
+    #[inline]
+    pub fn sources(&self) -> Vec<&str> {
+        self.sources.iter().map(String::as_str).collect()
+    }
+
+    /// Return the file and line which produced line `line` of `code`, starting from 1.
+    ///
+    /// Only needed for messages which do not follow the `#line` directives,
+    /// diagnostics of the driver already refer to `files` by their source number.
+    #[inline]
+    pub fn origin(&self, line: u32) -> Option<(&str, u32)> {
+        let &(file, line) = self.lines.get((line as usize).checked_sub(1)?)?;
+        Some((&self.files[file as usize], line))
+    }
+
+    /// Render `diagnostic` with the path and line of the file it points at.
+    #[inline]
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let index = diagnostic.source as usize;
+        match self.files.get(index) {
+            Some(file) => diagnostic.render_file(file, Some(&self.sources[index])),
+            None => diagnostic.render(&[]),
+        }
+    }
+
+    /// Render every diagnostic of `diagnostics` separated by blank lines.
+    pub fn render_all(&self, diagnostics: &[Diagnostic]) -> String {
+        diagnostics
+            .iter()
+            .map(|diagnostic| self.render(diagnostic))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Expand `#include "path"` directives of GLSL code.
+///
+/// Paths are relative to the including file, or to the root of the provider
+/// if they start with `/`, and can not leave the root. A `#line` directive is inserted around every include so
+/// the driver reports the original line and the index of the file in `Preprocessed::files`.
+/// Files containing `#pragma once` are only included once, and `#version` is only kept
+/// in the first file.
+pub struct Preprocessor<P> {
+    provider: P,
+}
+
+impl<P: FileProvider> Preprocessor<P> {
+    #[inline]
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    #[inline]
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Read the file at `path` from the provider and expand its includes.
+    pub fn process(&self, path: &str) -> Result<Preprocessed, String> {
+        let path = normalize(path)?;
+        let code = self.provider.read(&path)?;
+        self.process_source(&path, &code)
+    }
+
+    /// Expand the includes of `code`, resolving relative paths as if it was at `path`.
+    pub fn process_source(&self, path: &str, code: &str) -> Result<Preprocessed, String> {
+        let mut state = State::default();
+        self.expand(&mut state, normalize(path)?, code.to_owned())?;
+        Ok(state.output)
+    }
+
+    fn expand(&self, state: &mut State, path: String, code: String) -> Result<(), String> {
+        let file = match state.output.files.iter().position(|file| *file == path) {
+            Some(file) => file as u32,
+            None => {
+                state.output.files.push(path.clone());
+                state.output.sources.push(code.clone());
+                (state.output.files.len() - 1) as u32
+            }
+        };
+        state.stack.push(path.clone());
+        for (number, line) in (1..).zip(code.lines()) {
+            match directive(line) {
+                Some(Directive::Include(target)) => {
+                    let target = resolve(&path, target)
+                        .map_err(|err| format!("{}:{}: {}", path, number, err))?;
+                    // A guarded file included by itself is skipped rather than a cycle.
+                    if state.once.contains(&target) {
+                        state.push("", file, number);
+                        continue;
+                    }
+                    if state.stack.contains(&target) {
+                        return Err(format!(
+                            "{}:{}: Include cycle {} -> {}",
+                            path,
+                            number,
+                            state.stack.join(" -> "),
+                            target
+                        ));
+                    }
+                    let code = self
+                        .provider
+                        .read(&target)
+                        .map_err(|err| format!("{}:{}: {}", path, number, err))?;
+                    let index = state.output.files.iter().position(|file| *file == target);
+                    let index = index.unwrap_or(state.output.files.len());
+                    state.push(&format!("#line 1 {}", index), file, number);
+                    self.expand(state, target, code)?;
+                    state.push(&format!("#line {} {}", number + 1, file), file, number);
+                }
+                Some(Directive::Once) => {
+                    state.once.insert(path.clone());
+                    state.push("", file, number);
+                }
+                Some(Directive::Version) if file != 0 => state.push("", file, number),
+                _ => state.push(line, file, number),
+            }
+        }
+        state.stack.pop();
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct State {
+    output: Preprocessed,
+    /// Paths of the files being expanded, to find cycles.
+    stack: Vec<String>,
+    /// Paths of the files containing `#pragma once`.
+    once: HashSet<String>,
+}
+
+impl State {
+    fn push(&mut self, line: &str, file: u32, number: u32) {
+        self.output.code.push_str(line);
+        self.output.code.push('\n');
+        self.output.lines.push((file, number));
+    }
+}
+
+enum Directive<'a> {
+    Include(&'a str),
+    Once,
+    Version,
+}
+
+fn directive(line: &str) -> Option<Directive<'_>> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start();
+    if let Some(rest) = rest.strip_prefix("include") {
+        let rest = rest.trim();
+        let path = match rest.chars().next()? {
+            '"' => rest[1..].split_once('"')?.0,
+            '<' => rest[1..].split_once('>')?.0,
+            _ => return None,
+        };
+        return Some(Directive::Include(path));
+    }
+    if rest.starts_with("version") {
+        return Some(Directive::Version);
+    }
+    let mut words = rest.split_whitespace();
+    if words.next() == Some("pragma") && words.next() == Some("once") {
+        return Some(Directive::Once);
+    }
+    None
+}
+
+/// Resolve `target` included from the file at `from`.
+fn resolve(from: &str, target: &str) -> Result<String, String> {
+    let path = match target.strip_prefix('/') {
+        Some(target) => normalize(target),
+        None => match from.rsplit_once('/') {
+            Some((directory, _)) => normalize(&format!("{}/{}", directory, target)),
+            None => normalize(target),
+        },
+    };
+    path.map_err(|_| format!("Include '{}' is outside of the root", target))
+}
+
+/// Remove `.` and `..` components and duplicated separators from `path`.
+///
+/// It will return `Err(String)` if `path` leaves the root of the provider.
+fn normalize(path: &str) -> Result<String, String> {
+    let mut components: Vec<&str> = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                if components.pop().is_none() {
+                    return Err(format!("Path '{}' is outside of the root", path));
+                }
+            }
+            _ => components.push(component),
+        }
+    }
+    Ok(components.join("/"))
+}
@@ -0,0 +1,201 @@
+use std::fmt::Display;
+
+use crate::{Context, Program, ShaderType};
+
+use super::ProgramCache;
+
+/// Error of `ProgramBuilder::build()`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProgramError {
+    /// The info log of every stage which failed to compile.
+    Compile(Vec<(ShaderType, String)>),
+    /// A pre-link hook failed.
+    Hook(String),
+    /// The info log of the program which failed to link.
+    Link(String),
+}
+
+impl Display for ProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProgramError::Compile(stages) => {
+                for (i, (ty, log)) in stages.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(
+                        f,
+                        "{} shader failed to compile:\n{}",
+                        stage_name(*ty),
+                        log.trim_end()
+                    )?;
+                }
+                Ok(())
+            }
+            ProgramError::Hook(message) => write!(f, "Pre-link hook failed: {}", message),
+            ProgramError::Link(log) => write!(f, "Program failed to link:\n{}", log.trim_end()),
+        }
+    }
+}
+
+impl std::error::Error for ProgramError {}
+
+fn stage_name(ty: ShaderType) -> &'static str {
+    match ty {
+        ShaderType::Vertex => "Vertex",
+        ShaderType::Fragmet => "Fragment",
+        ShaderType::Geometry => "Geometry",
+        ShaderType::TessControl => "Tessellation control",
+        ShaderType::TessEvaluation => "Tessellation evaluation",
+        ShaderType::Compute => "Compute",
+    }
+}
+
+type Hook<'a> = Box<dyn FnOnce(&Program) -> Result<(), String> + 'a>;
+
+/// Compile the stages of a program and link them in one go.
+///
+/// ```ignore
+/// let program = ProgramBuilder::new(&context)
+///     .vertex(include_str!("shader/test.vs"))
+///     .fragment(include_str!("shader/test.fs"))
+///     .attrib_location(0, "position")
+///     .build()?;
+/// ```
+///
+/// Every stage is compiled before reporting errors, so one `ProgramError`
+/// lists all of them. The shaders are detached and deleted after linking.
+pub struct ProgramBuilder<'a> {
+    context: &'a Context,
+    sources: Vec<(ShaderType, String)>,
+    hooks: Vec<Hook<'a>>,
+    /// Locations bound by `attrib_location(...)` and `frag_data_location(...)`,
+    /// which are part of the key of `build_cached(...)`.
+    bindings: Vec<String>,
+}
+
+impl<'a> ProgramBuilder<'a> {
+    #[inline]
+    pub fn new(context: &'a Context) -> Self {
+        Self {
+            context,
+            sources: Vec::new(),
+            hooks: Vec::new(),
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Add a stage of type `ty` compiled from `code`.
+    #[inline]
+    pub fn stage(mut self, ty: ShaderType, code: impl Into<String>) -> Self {
+        self.sources.push((ty, code.into()));
+        self
+    }
+
+    #[inline]
+    pub fn vertex(self, code: impl Into<String>) -> Self {
+        self.stage(ShaderType::Vertex, code)
+    }
+
+    #[inline]
+    pub fn fragment(self, code: impl Into<String>) -> Self {
+        self.stage(ShaderType::Fragmet, code)
+    }
+
+    #[inline]
+    pub fn geometry(self, code: impl Into<String>) -> Self {
+        self.stage(ShaderType::Geometry, code)
+    }
+
+    /// Add the tessellation control and tessellation evaluation stages.
+    #[inline]
+    pub fn tess(self, control: impl Into<String>, evaluation: impl Into<String>) -> Self {
+        self.stage(ShaderType::TessControl, control)
+            .stage(ShaderType::TessEvaluation, evaluation)
+    }
+
+    #[inline]
+    pub fn compute(self, code: impl Into<String>) -> Self {
+        self.stage(ShaderType::Compute, code)
+    }
+
+    /// Run `hook` after the shaders are attached and before the program is linked.
+    #[inline]
+    pub fn pre_link(mut self, hook: impl FnOnce(&Program) -> Result<(), String> + 'a) -> Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Bind the vertex input `name` to `location` before linking.
+    #[inline]
+    pub fn attrib_location(mut self, location: u32, name: &'a str) -> Self {
+        self.bindings.push(format!("attrib {} {}", location, name));
+        self.pre_link(move |program| program.bind_attrib_location(location, name))
+    }
+
+    /// Bind the fragment output `name` to the color number `location` before linking.
+    #[inline]
+    pub fn frag_data_location(mut self, location: u32, name: &'a str) -> Self {
+        self.bindings
+            .push(format!("frag_data {} {}", location, name));
+        self.pre_link(move |program| program.bind_frag_data_location(location, name))
+    }
+
+    /// Compile every stage, run the pre-link hooks and link the program.
+    pub fn build(self) -> Result<Program, ProgramError> {
+        let mut shaders = Vec::with_capacity(self.sources.len());
+        let mut errors = Vec::new();
+        for (ty, code) in &self.sources {
+            let shader = self.context.new_shader(*ty);
+            shader.source(code);
+            match shader.compile() {
+                Ok(()) => shaders.push(shader),
+                Err(log) => errors.push((*ty, log)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(ProgramError::Compile(errors));
+        }
+
+        let program = self.context.new_program();
+        for shader in &shaders {
+            program.attach(shader);
+        }
+        let result = self
+            .hooks
+            .into_iter()
+            .try_for_each(|hook| hook(&program))
+            .map_err(ProgramError::Hook)
+            .and_then(|()| program.link().map_err(ProgramError::Link));
+        for shader in &shaders {
+            program.detach(shader);
+        }
+        result.map(|()| program)
+    }
+
+    /// Load the program from the binary in `cache`, or build it and store its binary.
+    ///
+    /// The key covers the sources and the locations bound by `attrib_location(...)` and
+    /// `frag_data_location(...)`, but not what other pre-link hooks do.
+    /// Failing to store the binary is not an error, the program is still returned.
+    pub fn build_cached(self, cache: &ProgramCache) -> Result<Program, ProgramError> {
+        let stages: Vec<_> = self
+            .sources
+            .iter()
+            .map(|(ty, code)| (*ty, code.as_str()))
+            .collect();
+        let bindings: Vec<_> = self.bindings.iter().map(String::as_str).collect();
+        let key = cache.key(&stages, &bindings);
+        if let Some(program) = cache.load(self.context, key) {
+            return Ok(program);
+        }
+        let program = self
+            .pre_link(|program| {
+                program.binary_retrievable_hint();
+                Ok(())
+            })
+            .build()?;
+        let _ = cache.store(key, &program);
+        Ok(program)
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    io::Write,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{Context, Program, ShaderType};
+
+/// Identifies the files written by `ProgramCache`.
+const MAGIC: &[u8; 4] = b"GLPB";
+
+/// Size of the header: the magic, the binary format, the length and the hash of the data.
+const HEADER: usize = MAGIC.len() + 4 + 8 + 8;
+
+/// Numbers the temporary files of one process, so concurrent stores never share one.
+static TEMPORARY: AtomicUsize = AtomicUsize::new(0);
+
+/// Directory of program binaries, keyed by a hash of the sources and the driver.
+///
+/// It is used by `ProgramBuilder::build_cached(...)` to skip compiling and linking
+/// on later runs. Binaries rejected by the driver, e.g. after a driver update,
+/// are deleted and the program is built from source again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProgramCache {
+    directory: PathBuf,
+    driver: String,
+}
+
+impl ProgramCache {
+    /// Create a cache in `directory` for the driver of `context`.
+    ///
+    /// The directory is created when the first binary is stored.
+    pub fn new(context: &Context, directory: impl Into<PathBuf>) -> Self {
+        let driver = format!(
+            "{}\n{}\n{}",
+            context.vendor(),
+            context.renderer(),
+            context.version()
+        );
+        Self::with_driver(directory, driver)
+    }
+
+    /// Create a cache in `directory` for the driver identified by `driver`.
+    #[inline]
+    pub fn with_driver(directory: impl Into<PathBuf>, driver: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            driver: driver.into(),
+        }
+    }
+
+    #[inline]
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Return the key of a program built from `stages` with `defines`.
+    ///
+    /// The key is a 64-bit FNV-1a hash, which is stable across runs and platforms.
+    /// The order of `defines` does not matter.
+    pub fn key(&self, stages: &[(ShaderType, &str)], defines: &[&str]) -> u64 {
+        let mut hash = Fnv1a::new();
+        hash.write_str(&self.driver);
+        for &(ty, code) in stages {
+            hash.write(&[stage_tag(ty)]);
+            hash.write_str(code);
+        }
+        let mut defines = defines.to_vec();
+        defines.sort_unstable();
+        defines.dedup();
+        for define in defines {
+            hash.write_str(define);
+        }
+        hash.0
+    }
+
+    /// Return the path of the binary stored for `key`.
+    #[inline]
+    pub fn path(&self, key: u64) -> PathBuf {
+        self.directory.join(format!("{:016x}.bin", key))
+    }
+
+    /// Create a program from the binary stored for `key`.
+    ///
+    /// It will return `None` if there is no binary or the driver rejects it,
+    /// in which case the file is removed.
+    pub fn load(&self, context: &Context, key: u64) -> Option<Program> {
+        let (format, data) = self.load_binary(key)?;
+        let program = context.new_program();
+        match program.load_binary(format, &data) {
+            Ok(()) => Some(program),
+            Err(_) => {
+                let _ = std::fs::remove_file(self.path(key));
+                None
+            }
+        }
+    }
+
+    /// Return the binary format and data stored for `key`.
+    ///
+    /// It will return `None` if there is no binary or the file is truncated or corrupted,
+    /// in which case the file is removed.
+    pub fn load_binary(&self, key: u64) -> Option<(u32, Vec<u8>)> {
+        let path = self.path(key);
+        let mut bytes = std::fs::read(&path).ok()?;
+        match decode(&bytes) {
+            Some(format) => Some((format, bytes.split_off(HEADER))),
+            None => {
+                let _ = std::fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Store the binary of the linked `program` for `key`.
+    ///
+    /// The program must have been linked after `Program::binary_retrievable_hint()`.
+    pub fn store(&self, key: u64, program: &Program) -> Result<(), String> {
+        let (format, data) = program
+            .binary()
+            .ok_or_else(|| "The driver does not provide program binaries".to_owned())?;
+        self.store_binary(key, format, &data)
+    }
+
+    /// Store the binary `data` of `format` for `key`.
+    ///
+    /// The file is written next to its final path then renamed into place,
+    /// so other processes never read a partially written binary.
+    pub fn store_binary(&self, key: u64, format: u32, data: &[u8]) -> Result<(), String> {
+        let mut bytes = Vec::with_capacity(HEADER + data.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&format.to_le_bytes());
+        bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&checksum(data).to_le_bytes());
+        bytes.extend_from_slice(data);
+        let temporary = self.directory.join(format!(
+            "{:016x}.{}.{}.tmp",
+            key,
+            std::process::id(),
+            TEMPORARY.fetch_add(1, Ordering::Relaxed)
+        ));
+        let result = std::fs::create_dir_all(&self.directory)
+            .and_then(|()| {
+                let mut file = std::fs::File::create(&temporary)?;
+                file.write_all(&bytes)?;
+                file.sync_all()
+            })
+            .and_then(|()| std::fs::rename(&temporary, self.path(key)));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temporary);
+        }
+        result.map_err(|err| format!("Failed to store program binary: {}", err))
+    }
+}
+
+/// Check the header of a stored file against its data and return the binary format.
+///
+/// The data is the rest of the file after `HEADER` bytes.
+fn decode(bytes: &[u8]) -> Option<u32> {
+    let bytes = bytes.strip_prefix(MAGIC)?;
+    let (format, bytes) = bytes.split_first_chunk::<4>()?;
+    let (length, bytes) = bytes.split_first_chunk::<8>()?;
+    let (hash, data) = bytes.split_first_chunk::<8>()?;
+    let valid = u64::from_le_bytes(*length) == data.len() as u64
+        && u64::from_le_bytes(*hash) == checksum(data);
+    valid.then(|| u32::from_le_bytes(*format))
+}
+
+/// Return the FNV-1a hash of `data`, which detects truncated and corrupted files.
+#[inline]
+fn checksum(data: &[u8]) -> u64 {
+    let mut hash = Fnv1a::new();
+    hash.write(data);
+    hash.0
+}
+
+fn stage_tag(ty: ShaderType) -> u8 {
+    match ty {
+        ShaderType::Vertex => 0,
+        ShaderType::Fragmet => 1,
+        ShaderType::Geometry => 2,
+        ShaderType::TessControl => 3,
+        ShaderType::TessEvaluation => 4,
+        ShaderType::Compute => 5,
+    }
+}
+
+/// 64-bit FNV-1a, unlike `DefaultHasher` its output never changes.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    #[inline]
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// Write the length before the string so consecutive strings can not be confused.
+    #[inline]
+    fn write_str(&mut self, text: &str) {
+        self.write(&(text.len() as u64).to_le_bytes());
+        self.write(text.as_bytes());
+    }
+}
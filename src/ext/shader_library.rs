@@ -0,0 +1,167 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{Context, Program, ShaderType};
+
+use super::{ProgramBuilder, ProgramCache, ProgramError};
+
+/// Sources of one program and the variants compiled from them.
+struct Entry {
+    stages: Vec<(ShaderType, String)>,
+    /// Compiled variants, keyed by `ShaderLibrary::variant_key(...)`, including the failed ones.
+    variants: HashMap<Vec<String>, Result<Program, ProgramError>>,
+}
+
+/// Programs compiled from shared sources with different sets of `#define`s.
+///
+/// ```ignore
+/// let mut library = ShaderLibrary::new(&context);
+/// library.add("mesh", &[(ShaderType::Vertex, VS), (ShaderType::Fragmet, FS)]);
+/// let program = library.get("mesh", &["SKINNED", "LIGHTS=4"])?;
+/// ```
+///
+/// A variant is compiled the first time it is requested and cached afterwards,
+/// failed variants are cached too so they are only reported once by the driver.
+pub struct ShaderLibrary<'a> {
+    context: &'a Context,
+    entries: HashMap<String, Entry>,
+    cache: Option<ProgramCache>,
+}
+
+impl<'a> ShaderLibrary<'a> {
+    #[inline]
+    pub fn new(context: &'a Context) -> Self {
+        Self {
+            context,
+            entries: HashMap::new(),
+            cache: None,
+        }
+    }
+
+    /// Load and store the binaries of the variants in `cache`.
+    #[inline]
+    pub fn set_cache(&mut self, cache: Option<ProgramCache>) {
+        self.cache = cache;
+    }
+
+    /// Add the stages of the program `id`, replacing its sources and variants if any.
+    pub fn add(&mut self, id: impl Into<String>, stages: &[(ShaderType, &str)]) {
+        let stages = stages
+            .iter()
+            .map(|&(ty, code)| (ty, code.to_owned()))
+            .collect();
+        self.entries.insert(
+            id.into(),
+            Entry {
+                stages,
+                variants: HashMap::new(),
+            },
+        );
+    }
+
+    /// Remove the program `id` and its variants.
+    #[inline]
+    pub fn remove(&mut self, id: &str) {
+        self.entries.remove(id);
+    }
+
+    /// Drop the compiled variants of `id`, which are compiled again when requested.
+    #[inline]
+    pub fn invalidate(&mut self, id: &str) {
+        if let Some(entry) = self.entries.get_mut(id) {
+            entry.variants.clear();
+        }
+    }
+
+    /// Return the variant of `id` compiled with `defines`, compiling it if needed.
+    ///
+    /// A define is either `NAME` or `NAME=VALUE`, their order does not matter.
+    /// It will return `Err(String)` if `id` is unknown or the variant failed to build.
+    pub fn get(&mut self, id: &str, defines: &[&str]) -> Result<&Program, String> {
+        let (context, cache) = (self.context, self.cache.as_ref());
+        let entry = self
+            .entries
+            .get_mut(id)
+            .ok_or_else(|| format!("Shader '{}' not found", id))?;
+        let key = Self::variant_key(defines);
+        let variant = entry.variants.entry(key).or_insert_with_key(|key| {
+            let defines: Vec<&str> = key.iter().map(String::as_str).collect();
+            let mut builder = ProgramBuilder::new(context);
+            for (ty, code) in &entry.stages {
+                builder = builder.stage(*ty, Self::insert_defines(code, &defines));
+            }
+            match cache {
+                Some(cache) => builder.build_cached(cache),
+                None => builder.build(),
+            }
+        });
+        variant
+            .as_ref()
+            .map_err(|err| format!("Shader '{}' with [{}]: {}", id, defines.join(", "), err))
+    }
+
+    /// Iterate the id, defines and error of every variant which failed to build.
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &[String], &ProgramError)> {
+        self.entries.iter().flat_map(|(id, entry)| {
+            entry
+                .variants
+                .iter()
+                .filter_map(move |(defines, variant)| match variant {
+                    Ok(_) => None,
+                    Err(err) => Some((id.as_str(), defines.as_slice(), err)),
+                })
+        })
+    }
+
+    /// Return the key of the variant compiled with `defines`.
+    ///
+    /// The defines are normalized to `NAME` or `NAME=VALUE` without spaces around the name
+    /// and the value, then sorted and deduplicated so the same set always gives the same key.
+    pub fn variant_key(defines: &[&str]) -> Vec<String> {
+        let mut key: Vec<String> = defines
+            .iter()
+            .map(|define| match define.split_once('=') {
+                Some((name, value)) => format!("{}={}", name.trim(), value.trim()),
+                None => define.trim().to_owned(),
+            })
+            .collect();
+        key.sort();
+        key.dedup();
+        key
+    }
+
+    /// Insert a `#define` for each of `defines` after the `#version` line of `code`,
+    /// followed by a `#line` directive so the driver reports the original line numbers.
+    pub fn insert_defines(code: &str, defines: &[&str]) -> String {
+        if defines.is_empty() {
+            return code.to_owned();
+        }
+        let mut block = String::new();
+        for define in defines {
+            let _ = match define.split_once('=') {
+                Some((name, value)) => writeln!(block, "#define {} {}", name.trim(), value.trim()),
+                None => writeln!(block, "#define {}", define.trim()),
+            };
+        }
+
+        let version = code
+            .lines()
+            .position(|line| line.trim_start().starts_with("#version"));
+        match version {
+            Some(index) => {
+                let mut output = String::with_capacity(code.len() + block.len());
+                let mut lines = code.split_inclusive('\n');
+                for line in lines.by_ref().take(index + 1) {
+                    output.push_str(line);
+                }
+                if !output.ends_with('\n') {
+                    output.push('\n');
+                }
+                output.push_str(&block);
+                let _ = writeln!(output, "#line {} 0", index + 2);
+                output.extend(lines);
+                output
+            }
+            None => format!("{}#line 1 0\n{}", block, code),
+        }
+    }
+}
@@ -0,0 +1,389 @@
+//! Generators of common shapes as indexed triangle lists.
+//!
+//! All shapes are centered at the origin, Y is up and front faces wind counter-clockwise.
+//! Every vertex has a unit normal, texture coordinates and a tangent along increasing `u`.
+
+use std::{collections::HashMap, f32::consts::PI};
+
+use crate::{Context, Mode, Target, Usage};
+
+use super::{
+    vector::{add, normalize, scale},
+    Aabb, Indices, Mesh, MeshVertex, Vertex,
+};
+
+/// Indexed triangle list produced by the generators of this module.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Shape {
+    pub vertices: Vec<MeshVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl Shape {
+    /// Return the bounding box of all vertices.
+    #[inline]
+    pub fn bounds(&self) -> Aabb {
+        Aabb::from_points(self.vertices.iter().map(|vertex| vertex.position))
+    }
+
+    /// Upload the shape into a mesh of triangles.
+    pub fn to_mesh(&self, context: &Context) -> Mesh {
+        let indices = Some(Indices::U32(&self.indices));
+        Mesh::new(context, &self.vertices, indices, Mode::Triangles)
+    }
+
+    /// Upload the shape into a vertex object with a vertex and an element buffer,
+    /// to be drawn by `draw_elements(Mode::Triangles, indices.len(), GlType::u32, 0)`.
+    pub fn to_vertex<'a>(&self, context: &'a Context) -> Vertex<'a> {
+        let mut vertex = Vertex::new(context);
+        vertex.new_vertex_buffer(&self.vertices, Usage::StaticDraw);
+        vertex.new_buffer(|ebo| {
+            ebo.bind(Target::ElementArray);
+            ebo.data(&self.indices, Usage::StaticDraw);
+        });
+        vertex
+    }
+
+    /// Append a grid of `(columns + 1) * (rows + 1)` vertices made by `f(i, j)`.
+    ///
+    /// The vertex `(i + 1, j + 1)` must lie in the direction of increasing `u` and `v`
+    /// from `(i, j)` for the triangles to face along the normals.
+    fn surface<F>(&mut self, columns: u32, rows: u32, mut f: F)
+    where
+        F: FnMut(u32, u32) -> MeshVertex,
+    {
+        let first = self.vertices.len() as u32;
+        for j in 0..=rows {
+            for i in 0..=columns {
+                self.vertices.push(f(i, j));
+            }
+        }
+        let index = |i: u32, j: u32| first + j * (columns + 1) + i;
+        for j in 0..rows {
+            for i in 0..columns {
+                let (a, b) = (index(i, j), index(i + 1, j));
+                let (c, d) = (index(i + 1, j + 1), index(i, j + 1));
+                self.indices.extend([a, b, c, a, c, d]);
+            }
+        }
+    }
+
+    /// Append a disc at height `y` facing up or down.
+    fn disc(&mut self, y: f32, radius: f32, segments: u32, up: bool) {
+        let first = self.vertices.len() as u32;
+        let normal = [0.0, if up { 1.0 } else { -1.0 }, 0.0];
+        let flip = if up { -1.0 } else { 1.0 };
+        self.vertices
+            .push(vertex([0.0, y, 0.0], normal, [0.5, 0.5], [1.0, 0.0, 0.0]));
+        for k in 0..=segments {
+            let (sin, cos) = (2.0 * PI * k as f32 / segments as f32).sin_cos();
+            let uv = [0.5 + 0.5 * sin, 0.5 + 0.5 * flip * cos];
+            let position = [radius * sin, y, radius * cos];
+            self.vertices
+                .push(vertex(position, normal, uv, [1.0, 0.0, 0.0]));
+        }
+        for k in 0..segments {
+            let (a, b) = (first + 1 + k, first + 2 + k);
+            if up {
+                self.indices.extend([first, a, b]);
+            } else {
+                self.indices.extend([first, b, a]);
+            }
+        }
+    }
+}
+
+/// Create a plane of `width` along X and `depth` along Z facing up,
+/// divided into `columns` by `rows` cells.
+pub fn plane(width: f32, depth: f32, columns: u32, rows: u32) -> Shape {
+    let (columns, rows) = (columns.max(1), rows.max(1));
+    let mut shape = Shape::default();
+    shape.surface(columns, rows, |i, j| {
+        let (u, v) = (i as f32 / columns as f32, j as f32 / rows as f32);
+        let position = [(u - 0.5) * width, 0.0, (0.5 - v) * depth];
+        vertex(position, [0.0, 1.0, 0.0], [u, v], [1.0, 0.0, 0.0])
+    });
+    shape
+}
+
+/// Create a cube of edge length `size`, each face divided into `subdivisions` squared cells.
+///
+/// Every face has its own vertices and covers the whole texture.
+pub fn cube(size: f32, subdivisions: u32) -> Shape {
+    // Normal, tangent and bitangent of each face.
+    const FACES: [[[f32; 3]; 3]; 6] = [
+        [[1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]],
+        [[-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]],
+        [[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]],
+        [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+        [[0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        [[0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+    ];
+    let n = subdivisions.max(1);
+    let mut shape = Shape::default();
+    for [normal, tangent, bitangent] in FACES {
+        shape.surface(n, n, |i, j| {
+            let (u, v) = (i as f32 / n as f32, j as f32 / n as f32);
+            let position = std::array::from_fn(|k| {
+                size * (0.5 * normal[k] + (u - 0.5) * tangent[k] + (v - 0.5) * bitangent[k])
+            });
+            vertex(position, normal, [u, v], tangent)
+        });
+    }
+    shape
+}
+
+/// Create a sphere of `segments` around Y and `rings` from pole to pole.
+///
+/// `u` starts at +Z and turns towards +X, `v` goes from the bottom to the top.
+pub fn uv_sphere(radius: f32, segments: u32, rings: u32) -> Shape {
+    let (segments, rings) = (segments.max(3), rings.max(2));
+    let mut shape = Shape::default();
+    shape.surface(segments, rings, |i, j| {
+        let (u, v) = (i as f32 / segments as f32, j as f32 / rings as f32);
+        let normal = sphere_normal(2.0 * PI * u, PI * (1.0 - v));
+        vertex(
+            scale(normal, radius),
+            normal,
+            [u, v],
+            around_y(2.0 * PI * u),
+        )
+    });
+    shape
+}
+
+/// Create a sphere by splitting the faces of an icosahedron `subdivisions` times.
+///
+/// The texture is mapped as on `uv_sphere(...)`, vertices on the seam are duplicated.
+pub fn icosphere(radius: f32, subdivisions: u32) -> Shape {
+    let t = (1.0 + 5f32.sqrt()) / 2.0;
+    let mut positions: Vec<[f32; 3]> = [
+        [-1.0, t, 0.0],
+        [1.0, t, 0.0],
+        [-1.0, -t, 0.0],
+        [1.0, -t, 0.0],
+        [0.0, -1.0, t],
+        [0.0, 1.0, t],
+        [0.0, -1.0, -t],
+        [0.0, 1.0, -t],
+        [t, 0.0, -1.0],
+        [t, 0.0, 1.0],
+        [-t, 0.0, -1.0],
+        [-t, 0.0, 1.0],
+    ]
+    .into_iter()
+    .map(normalize)
+    .collect();
+    let mut triangles: Vec<[u32; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoints = HashMap::new();
+        let mut midpoint = |a: u32, b: u32| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let (a, b) = (positions[a as usize], positions[b as usize]);
+                positions.push(normalize(add(a, b)));
+                positions.len() as u32 - 1
+            })
+        };
+        triangles = triangles
+            .into_iter()
+            .flat_map(|[a, b, c]| {
+                let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+
+    let mut vertices: Vec<MeshVertex> = positions
+        .iter()
+        .map(|&normal| {
+            let u = normal[0].atan2(normal[2]).rem_euclid(2.0 * PI) / (2.0 * PI);
+            let v = 0.5 + normal[1].clamp(-1.0, 1.0).asin() / PI;
+            vertex(
+                scale(normal, radius),
+                normal,
+                [u, v],
+                around_y(2.0 * PI * u),
+            )
+        })
+        .collect();
+    // The poles have no `u`, they are given one per triangle after the seam is fixed.
+    let is_pole = |vertex: &MeshVertex| vertex.normal[0].abs() + vertex.normal[2].abs() < 1e-6;
+    // Triangles crossing the seam get copies of their vertices left of it moved by 1 in `u`.
+    let mut copies = HashMap::new();
+    for triangle in &mut triangles {
+        let us = triangle.map(|index| {
+            let vertex = &vertices[index as usize];
+            (!is_pole(vertex)).then_some(vertex.uv[0])
+        });
+        let (min, max) = us
+            .iter()
+            .flatten()
+            .fold((1f32, 0f32), |(min, max), &u| (min.min(u), max.max(u)));
+        if max - min <= 0.5 {
+            continue;
+        }
+        for (index, u) in triangle.iter_mut().zip(us) {
+            if u.is_some_and(|u| u < 0.5) {
+                *index = *copies.entry(*index).or_insert_with(|| {
+                    let mut copy = vertices[*index as usize];
+                    copy.uv[0] += 1.0;
+                    vertices.push(copy);
+                    vertices.len() as u32 - 1
+                });
+            }
+        }
+    }
+    for triangle in &mut triangles {
+        for k in 0..3 {
+            let pole = vertices[triangle[k] as usize];
+            if !is_pole(&pole) {
+                continue;
+            }
+            let (a, b) = (triangle[(k + 1) % 3], triangle[(k + 2) % 3]);
+            let u = 0.5 * (vertices[a as usize].uv[0] + vertices[b as usize].uv[0]);
+            let tangent = around_y(2.0 * PI * u);
+            vertices.push(vertex(pole.position, pole.normal, [u, pole.uv[1]], tangent));
+            triangle[k] = vertices.len() as u32 - 1;
+        }
+    }
+    Shape {
+        vertices,
+        indices: triangles.into_iter().flatten().collect(),
+    }
+}
+
+/// Create a closed cylinder along Y with `segments` around it and `stacks` along it.
+pub fn cylinder(radius: f32, height: f32, segments: u32, stacks: u32) -> Shape {
+    let (segments, stacks) = (segments.max(3), stacks.max(1));
+    let mut shape = Shape::default();
+    shape.surface(segments, stacks, |i, j| {
+        let (u, v) = (i as f32 / segments as f32, j as f32 / stacks as f32);
+        let (sin, cos) = (2.0 * PI * u).sin_cos();
+        let position = [radius * sin, (v - 0.5) * height, radius * cos];
+        vertex(position, [sin, 0.0, cos], [u, v], around_y(2.0 * PI * u))
+    });
+    shape.disc(0.5 * height, radius, segments, true);
+    shape.disc(-0.5 * height, radius, segments, false);
+    shape
+}
+
+/// Create a closed cone along Y with the apex at the top,
+/// `segments` around it and `stacks` along it.
+pub fn cone(radius: f32, height: f32, segments: u32, stacks: u32) -> Shape {
+    let (segments, stacks) = (segments.max(3), stacks.max(1));
+    let slant = (radius * radius + height * height).sqrt();
+    let mut shape = Shape::default();
+    shape.surface(segments, stacks, |i, j| {
+        let (u, v) = (i as f32 / segments as f32, j as f32 / stacks as f32);
+        let (sin, cos) = (2.0 * PI * u).sin_cos();
+        let r = radius * (1.0 - v);
+        let position = [r * sin, (v - 0.5) * height, r * cos];
+        let normal = [height * sin / slant, radius / slant, height * cos / slant];
+        vertex(position, normal, [u, v], around_y(2.0 * PI * u))
+    });
+    shape.disc(-0.5 * height, radius, segments, false);
+    shape
+}
+
+/// Create a torus around Y with `segments` along the ring and `sides` around the tube.
+pub fn torus(major_radius: f32, minor_radius: f32, segments: u32, sides: u32) -> Shape {
+    let (segments, sides) = (segments.max(3), sides.max(3));
+    let mut shape = Shape::default();
+    shape.surface(segments, sides, |i, j| {
+        let (u, v) = (i as f32 / segments as f32, j as f32 / sides as f32);
+        let (sin, cos) = (2.0 * PI * u).sin_cos();
+        let (tube_sin, tube_cos) = (2.0 * PI * v).sin_cos();
+        let normal = [tube_cos * sin, tube_sin, tube_cos * cos];
+        let center = [major_radius * sin, 0.0, major_radius * cos];
+        let position = add(center, scale(normal, minor_radius));
+        vertex(position, normal, [u, v], around_y(2.0 * PI * u))
+    });
+    shape
+}
+
+/// Create a capsule along Y made of a cylinder of `height` closed by two hemispheres,
+/// with `segments` around it and `rings` on each hemisphere.
+///
+/// The total height is `height + 2 * radius`, `v` follows the length of the outline.
+pub fn capsule(radius: f32, height: f32, segments: u32, rings: u32) -> Shape {
+    let (segments, rings) = (segments.max(3), rings.max(1));
+    let length = PI * radius + height;
+    // The polar angle and height of each row, the equator appears once on each hemisphere.
+    let step = 0.5 * PI / rings as f32;
+    let bottom = (0..=rings).map(|j| (PI - step * j as f32, -0.5 * height));
+    let top = (0..=rings).map(|j| (0.5 * PI - step * j as f32, 0.5 * height));
+    let rows: Vec<(f32, f32)> = bottom.chain(top).collect();
+    let mut shape = Shape::default();
+    shape.surface(segments, 2 * rings + 1, |i, j| {
+        let u = i as f32 / segments as f32;
+        let (theta, y) = rows[j as usize];
+        let top = if j > rings { height } else { 0.0 };
+        let v = (radius * (PI - theta) + top) / length;
+        let normal = sphere_normal(2.0 * PI * u, theta);
+        let position = add(scale(normal, radius), [0.0, y, 0.0]);
+        vertex(position, normal, [u, v], around_y(2.0 * PI * u))
+    });
+    shape
+}
+
+/// Create a triangle covering the whole screen in normalized device coordinates,
+/// with texture coordinates spanning `[0, 1]` on the visible part.
+pub fn fullscreen_triangle() -> Shape {
+    let normal = [0.0, 0.0, 1.0];
+    let tangent = [1.0, 0.0, 0.0];
+    Shape {
+        vertices: vec![
+            vertex([-1.0, -1.0, 0.0], normal, [0.0, 0.0], tangent),
+            vertex([3.0, -1.0, 0.0], normal, [2.0, 0.0], tangent),
+            vertex([-1.0, 3.0, 0.0], normal, [0.0, 2.0], tangent),
+        ],
+        indices: vec![0, 1, 2],
+    }
+}
+
+#[inline]
+fn vertex(position: [f32; 3], normal: [f32; 3], uv: [f32; 2], tangent: [f32; 3]) -> MeshVertex {
+    MeshVertex {
+        position,
+        normal,
+        uv,
+        tangent: [tangent[0], tangent[1], tangent[2], 1.0],
+    }
+}
+
+/// Unit vector at the azimuth `phi` from +Z towards +X and the polar angle `theta` from +Y.
+#[inline]
+fn sphere_normal(phi: f32, theta: f32) -> [f32; 3] {
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    let (sin_theta, cos_theta) = theta.sin_cos();
+    [sin_theta * sin_phi, cos_theta, sin_theta * cos_phi]
+}
+
+/// Tangent of the circle around Y at the azimuth `phi`.
+#[inline]
+fn around_y(phi: f32) -> [f32; 3] {
+    let (sin, cos) = phi.sin_cos();
+    [cos, 0.0, -sin]
+}
@@ -0,0 +1,138 @@
+use crate::{Buffer, BufferFlag, Context, Fence, SyncStatus};
+
+/// Persistently mapped ring buffer for per-frame streaming data.
+///
+/// The buffer is partitioned into several frame regions. Data written during a frame
+/// is sub-allocated from the current region, and a fence is inserted when the frame
+/// ends. The CPU only waits when it wraps onto a region the GPU may still be reading.
+pub struct StreamBuffer<'a> {
+    context: &'a Context,
+    buffer: Buffer,
+    ptr: *mut u8,
+    frame_size: usize,
+    alignment: usize,
+    frame: usize,
+    head: usize,
+    fences: Vec<Option<Fence>>,
+}
+
+impl<'a> StreamBuffer<'a> {
+    /// Create a stream buffer with `frames` regions of `frame_size` bytes each.
+    ///
+    /// Sub-allocations are aligned to `GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT`.
+    pub fn new(context: &'a Context, frame_size: usize, frames: usize) -> Self {
+        let alignment = context.uniform_buffer_offset_alignment();
+        Self::with_alignment(context, frame_size, frames, alignment)
+    }
+
+    /// Create a stream buffer whose sub-allocations are aligned to `alignment` bytes.
+    pub fn with_alignment(
+        context: &'a Context,
+        frame_size: usize,
+        frames: usize,
+        alignment: usize,
+    ) -> Self {
+        assert!(frames > 0, "The number of frames must be greater than 0");
+        assert!(
+            alignment.is_power_of_two(),
+            "The alignment must be a power of two"
+        );
+        let frame_size = align_up(frame_size, alignment);
+        let size = frame_size * frames;
+        let flags = BufferFlag::MAP_WRITE_BIT
+            | BufferFlag::MAP_PERSISTENT_BIT
+            | BufferFlag::MAP_COHERENT_BIT;
+
+        let buffer = unsafe { context.new_buffer() };
+        buffer.storage(size, flags);
+        let ptr = unsafe { buffer.map_range(0, size, flags) } as *mut u8;
+        assert!(!ptr.is_null(), "Failed to map the stream buffer");
+
+        Self {
+            context,
+            buffer,
+            ptr,
+            frame_size,
+            alignment,
+            frame: 0,
+            head: 0,
+            fences: (0..frames).map(|_| None).collect(),
+        }
+    }
+
+    /// Sub-allocate `size` bytes from the current frame region.
+    ///
+    /// It will return the offset of the allocation in the whole buffer and the
+    /// writable memory, or `None` if the current region does not have enough space.
+    pub fn alloc(&mut self, size: usize) -> Option<(usize, &mut [u8])> {
+        let start = align_up(self.head, self.alignment);
+        let end = start.checked_add(size)?;
+        if end > self.frame_size {
+            return None;
+        }
+        self.wait_region();
+        self.head = end;
+        let offset = self.frame * self.frame_size + start;
+        let data = unsafe { std::slice::from_raw_parts_mut(self.ptr.add(offset), size) };
+        Some((offset, data))
+    }
+
+    /// Copy `data` into the current frame region.
+    ///
+    /// It will return the offset of the data in the whole buffer,
+    /// or `None` if the current region does not have enough space.
+    pub fn push<T: Copy>(&mut self, data: &[T]) -> Option<usize> {
+        let size = size_of_val(data);
+        let (offset, dst) = self.alloc(size)?;
+        let src = unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, size) };
+        dst.copy_from_slice(src);
+        Some(offset)
+    }
+
+    /// Finish the current frame.
+    ///
+    /// A fence is inserted after the commands of this frame and the next
+    /// region becomes current.
+    pub fn end_frame(&mut self) {
+        self.fences[self.frame] = Some(self.context.new_fence());
+        self.frame = (self.frame + 1) % self.fences.len();
+        self.head = 0;
+    }
+
+    /// Return the underlying buffer.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Return the size of each frame region in bytes.
+    #[inline]
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    /// Return the alignment of sub-allocations in bytes.
+    #[inline]
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Block until the GPU finished reading the current region, if needed.
+    fn wait_region(&mut self) {
+        if let Some(fence) = self.fences[self.frame].take() {
+            while fence.client_wait(1_000_000_000) == SyncStatus::TimeoutExpired {}
+        }
+    }
+}
+
+impl Drop for StreamBuffer<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.buffer.unmap();
+    }
+}
+
+#[inline]
+const fn align_up(value: usize, alignment: usize) -> usize {
+    (value + alignment - 1) & !(alignment - 1)
+}
@@ -0,0 +1,79 @@
+use std::marker::PhantomData;
+
+use crate::{Buffer, BufferFlag, Context, IndexedTarget, Program, Std140};
+
+/// Buffer holding one value of `T` in the `std140` layout of a uniform block.
+///
+/// ```ignore
+/// #[derive(Std140)]
+/// struct Camera {
+///     view: Mat4<f32>,
+///     position: Vec3<f32>,
+/// }
+///
+/// let camera = UniformBuffer::new(&context, &Camera { ... });
+/// camera.bind_block(&program, "Camera", 0)?;
+/// ```
+pub struct UniformBuffer<T> {
+    buffer: Buffer,
+    bytes: Vec<u8>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Std140> UniformBuffer<T> {
+    /// Create a uniform buffer initialized with `value`.
+    pub fn new(context: &Context, value: &T) -> Self {
+        let bytes = value.to_std140();
+        let buffer = unsafe { context.new_buffer() };
+        buffer.storage_with(&bytes, BufferFlag::DYNAMIC_STORAGE_BIT);
+        Self {
+            buffer,
+            bytes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Upload `value` to the buffer.
+    pub fn set(&mut self, value: &T) {
+        value.write_std140(&mut self.bytes);
+        unsafe {
+            self.buffer.sub_data(0, &self.bytes);
+        }
+    }
+
+    /// Bind the buffer to the uniform buffer binding point `binding`.
+    ///
+    /// Wrapper of `glBindBufferBase(GL_UNIFORM_BUFFER, ...)`
+    #[inline]
+    pub fn bind(&self, binding: u32) {
+        self.buffer.bind_base(IndexedTarget::Uniform, binding);
+    }
+
+    /// Connect the uniform block `name` of `program` to `binding` and bind the buffer to it.
+    ///
+    /// It will return `Err(String)` if the block does not exist or
+    /// is larger than `T`, which usually means the struct does not match the block.
+    pub fn bind_block(&self, program: &Program, name: &str, binding: u32) -> Result<(), String> {
+        let reflection = program.reflect();
+        let block = reflection
+            .uniform_block(name)
+            .ok_or_else(|| format!("Uniform block '{}' not found", name))?;
+        if block.size > T::SIZE {
+            return Err(format!(
+                "Uniform block '{}' takes {} bytes, but {} only has {} bytes",
+                name,
+                block.size,
+                std::any::type_name::<T>(),
+                T::SIZE
+            ));
+        }
+        program.uniform_block_binding(name, binding)?;
+        self.bind(binding);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+}
@@ -1,10 +1,14 @@
-use crate::{Array, Buffer, Context};
+use std::any::TypeId;
+
+use crate::{Array, Buffer, Context, Target, Usage, VertexAttribute, VertexLayout};
 
 /// Vertex Object
 pub struct Vertex<'a> {
     context: &'a Context,
     array: Array,
     buffers: Vec<Buffer>,
+    /// Index of the per-instance buffer, its usage and the type of its elements.
+    instance: Option<(usize, Usage, TypeId)>,
 }
 
 impl<'a> Vertex<'a> {
@@ -17,6 +21,7 @@ impl<'a> Vertex<'a> {
             context,
             array,
             buffers,
+            instance: None,
         }
     }
 
@@ -33,6 +38,64 @@ impl<'a> Vertex<'a> {
         self.buffers.push(buffer);
     }
 
+    /// Create a new buffer holding `data` and mark its attributes by the layout of `V`.
+    #[inline]
+    pub fn new_vertex_buffer<V: VertexLayout + Copy>(&mut self, data: &[V], usage: Usage) {
+        self.new_buffer(|vbo| {
+            vbo.bind(Target::Array);
+            vbo.data(data, usage);
+            Buffer::mark_layout::<V>();
+        });
+    }
+
+    /// Create the per-instance buffer holding `data` and mark its attributes by the layout of `I`.
+    ///
+    /// Attributes of `I` without a divisor advance once per instance.
+    /// The data can be replaced later by `set_instance_data(...)`.
+    pub fn new_instance_buffer<I: VertexLayout + Copy + 'static>(
+        &mut self,
+        data: &[I],
+        usage: Usage,
+    ) {
+        let attributes: Vec<_> = I::ATTRIBUTES
+            .iter()
+            .map(|&attribute| VertexAttribute {
+                divisor: attribute.divisor.max(1),
+                ..attribute
+            })
+            .collect();
+        self.new_buffer(|vbo| {
+            vbo.bind(Target::Array);
+            vbo.data(data, usage);
+            Buffer::mark_attributes(&attributes, I::STRIDE);
+        });
+        self.instance = Some((self.buffers.len() - 1, usage, TypeId::of::<I>()));
+    }
+
+    /// Replace the data of the per-instance buffer, e.g. once per frame.
+    ///
+    /// # Panics
+    /// If no instance buffer has been created by `new_instance_buffer(...)`,
+    /// or it was created for another type than `I`.
+    #[inline]
+    pub fn set_instance_data<I: VertexLayout + Copy + 'static>(&self, data: &[I]) {
+        let (index, usage, ty) = self
+            .instance
+            .expect("The vertex object has no instance buffer");
+        assert!(
+            ty == TypeId::of::<I>(),
+            "The instance buffer does not hold {}",
+            std::any::type_name::<I>()
+        );
+        self.buffers[index].data(data, usage);
+    }
+
+    /// Return its array, e.g. to configure it by the direct state access methods.
+    #[inline]
+    pub fn array(&self) -> &Array {
+        &self.array
+    }
+
     /// Bind its array to the current context.
     #[inline]
     pub fn using(&self) {
@@ -0,0 +1,183 @@
+use std::fmt::Display;
+
+use crate::{
+    AttribKind, GlType, GlslScalar, GlslType, Program, ProgramVariable, VertexAttribute,
+    VertexLayout,
+};
+
+/// Error found when matching a vertex layout against the inputs of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LayoutError {
+    /// The program reads an input which no attribute provides.
+    Missing { name: String, ty: GlslType },
+    /// The attribute can not be passed to the input.
+    TypeMismatch {
+        name: String,
+        expected: GlslType,
+        found: (GlType, usize, AttribKind),
+    },
+    /// The attribute and the input use different locations.
+    LocationMismatch {
+        name: String,
+        expected: i32,
+        found: u32,
+    },
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LayoutError::Missing { name, ty } => {
+                write!(
+                    f,
+                    "Input '{}' of type {:?} is not provided by the layout",
+                    name, ty
+                )
+            }
+            LayoutError::TypeMismatch {
+                name,
+                expected,
+                found: (ty, count, kind),
+            } => write!(
+                f,
+                "Attribute '{}' of {} x {:?} ({:?}) can not be passed to input of type {:?}",
+                name, count, ty, kind, expected
+            ),
+            LayoutError::LocationMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Attribute '{}' uses location {} but the input is at location {}",
+                name, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Active vertex inputs of a program, used to check vertex layouts by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VertexInputs {
+    inputs: Vec<ProgramVariable>,
+}
+
+impl VertexInputs {
+    /// Query the active vertex inputs of a linked `program`.
+    #[inline]
+    pub fn new(program: &Program) -> Self {
+        Self::from_variables(program.active_inputs())
+    }
+
+    /// Create from already known inputs.
+    #[inline]
+    pub fn from_variables(inputs: Vec<ProgramVariable>) -> Self {
+        Self { inputs }
+    }
+
+    /// Return the inputs.
+    #[inline]
+    pub fn inputs(&self) -> &[ProgramVariable] {
+        &self.inputs
+    }
+
+    /// Check that every input is provided by an attribute of the same name
+    /// with a compatible type and the same location.
+    ///
+    /// Attributes which the program does not read are ignored.
+    pub fn validate(&self, attributes: &[VertexAttribute]) -> Result<(), Vec<LayoutError>> {
+        let mut errors = Vec::new();
+        for (input, attribute) in self.matches(attributes, &mut errors) {
+            if input.location >= 0 && input.location != attribute.location as i32 {
+                errors.push(LayoutError::LocationMismatch {
+                    name: input.name.clone(),
+                    expected: input.location,
+                    found: attribute.location,
+                });
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check the layout of the vertex type `V`, see `validate(...)`.
+    #[inline]
+    pub fn validate_layout<V: VertexLayout>(&self) -> Result<(), Vec<LayoutError>> {
+        self.validate(V::ATTRIBUTES)
+    }
+
+    /// Return the attributes moved to the locations of the inputs of the same name.
+    ///
+    /// Attributes which the program does not read are left out,
+    /// so the result can be marked directly.
+    pub fn assign(
+        &self,
+        attributes: &[VertexAttribute],
+    ) -> Result<Vec<VertexAttribute>, Vec<LayoutError>> {
+        let mut errors = Vec::new();
+        let assigned = self
+            .matches(attributes, &mut errors)
+            .into_iter()
+            .filter(|(input, _)| input.location >= 0)
+            .map(|(input, attribute)| VertexAttribute {
+                location: input.location as _,
+                ..*attribute
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(assigned)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Pair each input with the attribute of the same name, collecting missing and mismatched ones.
+    fn matches<'a, 'b>(
+        &'a self,
+        attributes: &'b [VertexAttribute],
+        errors: &mut Vec<LayoutError>,
+    ) -> Vec<(&'a ProgramVariable, &'b VertexAttribute)> {
+        let mut matches = Vec::new();
+        for input in &self.inputs {
+            let Some(attribute) = attributes.iter().find(|a| a.name == input.name) else {
+                errors.push(LayoutError::Missing {
+                    name: input.name.clone(),
+                    ty: input.ty,
+                });
+                continue;
+            };
+            if !Self::compatible(attribute, input) {
+                errors.push(LayoutError::TypeMismatch {
+                    name: input.name.clone(),
+                    expected: input.ty,
+                    found: (attribute.ty, attribute.count, attribute.kind),
+                });
+                continue;
+            }
+            matches.push((input, attribute));
+        }
+        matches
+    }
+
+    fn compatible(attribute: &VertexAttribute, input: &ProgramVariable) -> bool {
+        let Some(scalar) = input.ty.scalar() else {
+            return false;
+        };
+        let signed = matches!(attribute.ty, GlType::i8 | GlType::i16 | GlType::i32);
+        let kind = match attribute.kind {
+            AttribKind::Float | AttribKind::Normalized => scalar == GlslScalar::Float,
+            AttribKind::Integer => match scalar {
+                GlslScalar::Int => signed,
+                GlslScalar::UInt => !signed,
+                _ => false,
+            },
+            AttribKind::Double => scalar == GlslScalar::Double,
+        };
+        kind && attribute.locations as usize == input.ty.locations() * input.array_size
+    }
+}
@@ -7,3 +7,8 @@ pub use ext::*;
 pub const OPENGL_VERSION: (u32, u32) = (4, 5);
 
 pub extern crate mats;
+
+pub use opengl_derive::{Std140, Std430, VertexLayout};
+
+// Lets the derive macros refer to `::opengl` inside this crate too.
+extern crate self as opengl;
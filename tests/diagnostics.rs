@@ -0,0 +1,145 @@
+use opengl::*;
+
+const SOURCE: &str =
+    "#version 450 core\nvoid main() {\n    gl_Position = vec4(posiiton, 1.0);\n}\n";
+
+fn diagnostic(
+    severity: Severity,
+    line: Option<u32>,
+    column: Option<u32>,
+    message: &str,
+) -> Diagnostic {
+    Diagnostic {
+        severity,
+        source: 0,
+        line,
+        column,
+        message: message.to_owned(),
+    }
+}
+
+#[test]
+fn test_parse_mesa() {
+    let log = "0:3(24): error: `posiiton' undeclared\n0:3(19): warning: unused value\n";
+    assert_eq!(
+        Diagnostic::parse(log),
+        [
+            diagnostic(Severity::Error, Some(3), Some(24), "`posiiton' undeclared"),
+            diagnostic(Severity::Warning, Some(3), Some(19), "unused value"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_mesa_preprocessor() {
+    let log =
+        "0:1(10): preprocessor error: syntax error, unexpected IDENTIFIER, expecting NEWLINE\n\
+               0:4(1): preprocessor warning: Redefinition of macro SCALE\n\
+               0:5(3): error: `foo' undeclared\n";
+    assert_eq!(
+        Diagnostic::parse(log),
+        [
+            diagnostic(
+                Severity::Error,
+                Some(1),
+                Some(10),
+                "syntax error, unexpected IDENTIFIER, expecting NEWLINE"
+            ),
+            diagnostic(
+                Severity::Warning,
+                Some(4),
+                Some(1),
+                "Redefinition of macro SCALE"
+            ),
+            diagnostic(Severity::Error, Some(5), Some(3), "`foo' undeclared"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_nvidia() {
+    let log = "0(3) : error C1008: undefined variable \"posiiton\"\n0(3) : warning C7022: unrecognized profile specifier \"core\"\n";
+    assert_eq!(
+        Diagnostic::parse(log),
+        [
+            diagnostic(
+                Severity::Error,
+                Some(3),
+                None,
+                "undefined variable \"posiiton\""
+            ),
+            diagnostic(
+                Severity::Warning,
+                Some(3),
+                None,
+                "unrecognized profile specifier \"core\""
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_amd_and_intel() {
+    let log = "Vertex shader failed to compile with the following errors:\n\
+               ERROR: 0:3: 'posiiton' : undeclared identifier \n\
+               ERROR: 0:3: 'vec4' :  no matching overloaded function found\n\
+               ERROR: 2 compilation errors.  No code generated.\n";
+    assert_eq!(
+        Diagnostic::parse(log),
+        [
+            diagnostic(
+                Severity::Error,
+                Some(3),
+                None,
+                "'posiiton' : undeclared identifier"
+            ),
+            diagnostic(
+                Severity::Error,
+                Some(3),
+                None,
+                "'vec4' :  no matching overloaded function found"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_link_and_unknown() {
+    let log = "error: linking with uncompiled/unspecialized shader\nin program 3\n";
+    assert_eq!(
+        Diagnostic::parse(log),
+        [diagnostic(
+            Severity::Error,
+            None,
+            None,
+            "linking with uncompiled/unspecialized shader\nin program 3"
+        )],
+    );
+    assert_eq!(
+        Diagnostic::parse("Link failed for some reason\n"),
+        [diagnostic(
+            Severity::Error,
+            None,
+            None,
+            "Link failed for some reason"
+        )]
+    );
+    assert!(Diagnostic::parse("").is_empty());
+}
+
+#[test]
+fn test_render() {
+    let error = diagnostic(Severity::Error, Some(3), Some(24), "`posiiton' undeclared");
+    assert_eq!(
+        error.render(&[SOURCE]),
+        "error: `posiiton' undeclared\n --> 0:3:24\n  |\n3 |     gl_Position = vec4(posiiton, 1.0);\n  |                        ^"
+    );
+    let warning = diagnostic(Severity::Warning, Some(2), None, "something");
+    assert_eq!(
+        warning.render(&[SOURCE]),
+        "warning: something\n --> 0:2\n  |\n2 | void main() {\n  | ^^^^^^^^^^^^^"
+    );
+    assert_eq!(error.to_string(), "0:3:24: error: `posiiton' undeclared");
+    let missing = diagnostic(Severity::Error, Some(30), None, "out of range");
+    assert_eq!(missing.render(&[SOURCE]), "error: out of range\n --> 0:30");
+}
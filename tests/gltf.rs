@@ -0,0 +1,71 @@
+#![cfg(feature = "gltf")]
+
+use opengl::*;
+
+const SCENE: &str = r#"{
+    "asset": { "version": "2.0" },
+    "scene": 0,
+    "scenes": [{ "nodes": [0] }],
+    "nodes": [
+        { "name": "root", "children": [1, 2] },
+        { "name": "moved", "translation": [1, 2, 3] },
+        { "scale": [2, 2, 2] }
+    ],
+    "images": [{ "uri": "albedo.png" }],
+    "textures": [{ "source": 0 }],
+    "materials": [
+        {
+            "name": "painted",
+            "pbrMetallicRoughness": {
+                "baseColorFactor": [1, 0, 0, 1],
+                "baseColorTexture": { "index": 0, "texCoord": 1 },
+                "roughnessFactor": 0.25
+            },
+            "alphaMode": "MASK",
+            "doubleSided": true
+        },
+        {}
+    ]
+}"#;
+
+#[test]
+fn test_parse_gltf() {
+    let info = GltfSceneInfo::parse(SCENE.as_bytes()).unwrap();
+    assert_eq!(info.roots, [0]);
+    assert_eq!(info.nodes.len(), 3);
+    assert_eq!(info.nodes[0].name.as_deref(), Some("root"));
+    assert_eq!(info.nodes[0].children, [1, 2]);
+    assert_eq!(info.nodes[1].mesh, None);
+    // Transforms are stored in the row-major order of `mats`.
+    let transform: [[f32; 4]; 4] = info.nodes[1].transform.into();
+    assert_eq!(transform[0][3], 1.0);
+    assert_eq!(transform[1][3], 2.0);
+    assert_eq!(transform[2][3], 3.0);
+
+    assert_eq!(info.materials.len(), 2);
+    let painted = &info.materials[0];
+    assert_eq!(painted.name.as_deref(), Some("painted"));
+    assert_eq!(painted.base_color_factor, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(
+        painted.base_color_texture,
+        Some(TextureRef {
+            texture: 0,
+            tex_coord: 1
+        })
+    );
+    assert_eq!(painted.metallic_factor, 1.0);
+    assert_eq!(painted.roughness_factor, 0.25);
+    assert_eq!(painted.alpha_mode, AlphaMode::Mask(0.5));
+    assert!(painted.double_sided);
+
+    let default = &info.materials[1];
+    assert_eq!(default.base_color_texture, None);
+    assert_eq!(default.alpha_mode, AlphaMode::Opaque);
+    assert!(!default.double_sided);
+}
+
+#[test]
+fn test_parse_gltf_errors() {
+    assert!(GltfSceneInfo::parse(b"{}").is_err());
+    assert!(GltfSceneInfo::parse(b"not json").is_err());
+}
@@ -0,0 +1,99 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use opengl::*;
+
+fn directory(name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("opengl-hot-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+/// Write `code` to `path` and move its modification time forward by `seconds`,
+/// so the change is seen whatever the resolution of the file system.
+fn write(path: &Path, code: &str, seconds: u64) {
+    std::fs::write(path, code).unwrap();
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 + seconds);
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(time)
+        .unwrap();
+}
+
+#[test]
+fn test_watched_files() {
+    let directory = directory("watched");
+    let file = directory.join("a.glsl");
+    let mut watched = WatchedFiles::new();
+    watched.watch(&file);
+    assert!(!watched.changed());
+    write(&file, "void a();\n", 1);
+    assert!(watched.changed());
+    watched.watch(&file);
+    assert!(!watched.changed());
+    assert_eq!(watched.paths().count(), 1);
+    std::fs::remove_file(&file).unwrap();
+    assert!(watched.changed());
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_hot_sources_keep_watching_on_error() {
+    let directory = directory("sources");
+    let vertex = directory.join("main.vert");
+    let fragment = directory.join("main.frag");
+    let light = directory.join("light.glsl");
+    let common = directory.join("common.glsl");
+    write(&vertex, "#include \"common.glsl\"\nvoid main() {}\n", 1);
+    write(&fragment, "#include \"light.glsl\"\nvoid main() {}\n", 1);
+    write(&light, "#include \"common.glsl\"\nvoid light();\n", 1);
+    write(&common, "#pragma once\nvoid common();\n", 1);
+
+    let mut sources = HotSources::new(&[
+        (ShaderType::Vertex, &vertex),
+        (ShaderType::Fragmet, &fragment),
+    ]);
+    assert_eq!(sources.read().unwrap().len(), 2);
+    for file in [&vertex, &fragment, &light, &common] {
+        assert!(sources.watched().contains(file));
+    }
+    assert!(!sources.changed());
+
+    // The vertex stage fails, the includes of the fragment stage are still watched.
+    write(&vertex, "#include \"missing.glsl\"\nvoid main() {}\n", 2);
+    write(&light, "#include \"broken.glsl\"\nvoid light();\n", 2);
+    assert!(sources.changed());
+    let err = sources.read().unwrap_err();
+    assert!(err.contains("missing.glsl"));
+    assert!(err.contains("broken.glsl"));
+    let missing = directory.join("missing.glsl");
+    let broken = directory.join("broken.glsl");
+    for file in [&vertex, &fragment, &light, &common, &missing, &broken] {
+        assert!(sources.watched().contains(file));
+    }
+    assert!(!sources.changed());
+
+    // Fixing an include of the previous build is a change.
+    write(&common, "#pragma once\nvoid common2();\n", 3);
+    assert!(sources.changed());
+    assert!(sources.read().is_err());
+    assert!(!sources.changed());
+
+    // Creating the missing files is a change, too.
+    write(&missing, "void missing();\n", 4);
+    assert!(sources.changed());
+    assert!(sources.read().is_err());
+    write(&broken, "void broken();\n", 4);
+    assert!(sources.changed());
+    assert_eq!(sources.read().unwrap().len(), 2);
+    assert!(sources.watched().contains(&broken));
+    assert!(!sources.watched().contains(&common));
+    std::fs::remove_dir_all(&directory).unwrap();
+}
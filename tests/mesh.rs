@@ -0,0 +1,60 @@
+use opengl::*;
+
+#[test]
+fn test_aabb() {
+    let aabb = Aabb::from_points([[1.0, -2.0, 0.5], [-1.0, 3.0, 0.0], [0.0, 0.0, 2.5]]);
+    assert_eq!(aabb.min, [-1.0, -2.0, 0.0]);
+    assert_eq!(aabb.max, [1.0, 3.0, 2.5]);
+    assert_eq!(aabb.center(), [0.0, 0.5, 1.25]);
+    assert_eq!(aabb.size(), [2.0, 5.0, 2.5]);
+    assert!(!aabb.is_empty());
+    assert!(Aabb::from_points([]).is_empty());
+}
+
+#[test]
+fn test_aabb_from_vertices() {
+    let vertices = [
+        MeshVertex {
+            position: [1.0, -2.0, 0.5],
+            ..Default::default()
+        },
+        MeshVertex {
+            position: [-1.0, 3.0, 0.0],
+            uv: [8.0, 8.0],
+            ..Default::default()
+        },
+    ];
+    let aabb = Aabb::from_vertices(&vertices).unwrap();
+    assert_eq!(aabb.min, [-1.0, -2.0, 0.0]);
+    assert_eq!(aabb.max, [1.0, 3.0, 0.5]);
+
+    #[repr(C)]
+    #[derive(Clone, Copy, VertexLayout)]
+    struct Colored {
+        color: [u8; 4],
+        #[vertex(position)]
+        point: mats::Vec4<f32>,
+    }
+    let vertices = [Colored {
+        color: [255; 4],
+        point: mats::Vec4::from([[4.0, 5.0, 6.0, 1.0]]),
+    }];
+    let aabb = Aabb::from_vertices(&vertices).unwrap();
+    assert_eq!(aabb.min, [4.0, 5.0, 6.0]);
+
+    // Only the marked field is the position, whatever its name.
+    #[repr(C)]
+    #[derive(Clone, Copy, VertexLayout)]
+    struct Flat {
+        position: [f32; 3],
+    }
+    assert_eq!(Aabb::from_vertices(&[Flat { position: [0.0; 3] }]), None);
+}
+
+#[test]
+fn test_indices() {
+    let indices = Indices::U16(&[0, 1, 2, 2, 3, 0]);
+    assert_eq!(indices.len(), 6);
+    assert_eq!(indices.ty(), GlType::u16);
+    assert_eq!(SubMesh::from(3..6), SubMesh { first: 3, count: 3 });
+}
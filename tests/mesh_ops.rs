@@ -0,0 +1,290 @@
+use opengl::{mesh_ops, shapes, MeshVertex};
+
+/// Vertices of a `size` by `size` grid of quads in the XY plane and its triangles row by row.
+fn grid(size: u32) -> (Vec<MeshVertex>, Vec<u32>) {
+    let mut vertices = Vec::new();
+    for y in 0..=size {
+        for x in 0..=size {
+            vertices.push(MeshVertex {
+                position: [x as f32, y as f32, 0.0],
+                normal: [0.0, 0.0, 1.0],
+                uv: [x as f32 / size as f32, y as f32 / size as f32],
+                ..Default::default()
+            });
+        }
+    }
+    let mut indices = Vec::new();
+    for y in 0..size {
+        for x in 0..size {
+            let a = y * (size + 1) + x;
+            let (b, c, d) = (a + 1, a + size + 2, a + size + 1);
+            indices.extend([a, b, c, a, c, d]);
+        }
+    }
+    (vertices, indices)
+}
+
+fn sorted_triangles(indices: &[u32]) -> Vec<[u32; 3]> {
+    let mut triangles: Vec<[u32; 3]> = indices
+        .chunks(3)
+        .map(|t| {
+            // Rotate the smallest index first, which keeps the winding.
+            let k = (0..3).min_by_key(|&k| t[k]).unwrap();
+            [t[k], t[(k + 1) % 3], t[(k + 2) % 3]]
+        })
+        .collect();
+    triangles.sort();
+    triangles
+}
+
+#[test]
+fn test_normals() {
+    let (mut vertices, indices) = grid(4);
+    mesh_ops::smooth_normals(&mut vertices, &indices);
+    assert!(vertices.iter().all(|v| v.normal == [0.0, 0.0, 1.0]));
+
+    let mut cube = shapes::cube(1.0, 1);
+    let expected: Vec<_> = cube.vertices.iter().map(|v| v.normal).collect();
+    mesh_ops::smooth_normals(&mut cube.vertices, &cube.indices);
+    // The corners are shared by 3 faces at right angles.
+    let corner = 1.0 / 3f32.sqrt();
+    for (vertex, normal) in cube.vertices.iter().zip(expected) {
+        for (actual, expected) in vertex.normal.into_iter().zip(normal) {
+            assert!(expected == 0.0 || (actual - expected.signum() * corner).abs() < 1e-5);
+        }
+    }
+
+    let (flat, flat_indices) = mesh_ops::flat_normals(&cube.vertices, &cube.indices);
+    assert_eq!(flat.len(), 36);
+    assert_eq!(flat_indices, (0..36).collect::<Vec<_>>());
+    assert_eq!(flat[0].normal, [1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_tangents() {
+    for shape in [
+        shapes::cube(2.0, 2),
+        shapes::cylinder(1.0, 2.0, 16, 2),
+        shapes::torus(2.0, 0.5, 32, 16),
+    ] {
+        let (vertices, indices) = mesh_ops::tangents(&shape.vertices, &shape.indices);
+        assert_eq!(vertices.len(), shape.vertices.len());
+        assert_eq!(indices, shape.indices);
+        for (vertex, expected) in vertices.iter().zip(&shape.vertices) {
+            let cos: f32 = (0..3)
+                .map(|k| vertex.tangent[k] * expected.tangent[k])
+                .sum();
+            assert!(cos > 0.95, "{:?} {:?}", vertex.tangent, expected.tangent);
+            assert_eq!(vertex.tangent[3], 1.0);
+        }
+    }
+
+    // Mirrored texture coordinates flip the sign.
+    let (mut vertices, indices) = grid(1);
+    for vertex in &mut vertices {
+        vertex.normal = [0.0, 0.0, 1.0];
+        vertex.uv[0] = 1.0 - vertex.uv[0];
+    }
+    let (vertices, _) = mesh_ops::tangents(&vertices, &indices);
+    assert!(vertices.iter().all(|v| v.tangent == [-1.0, 0.0, 0.0, -1.0]));
+}
+
+#[test]
+fn test_tangents_mirrored_seam() {
+    // Two quads sharing the edge at x = 1, the right one mirrors the texture of the left one.
+    let vertex = |x: f32, y: f32| MeshVertex {
+        position: [x, y, 0.0],
+        normal: [0.0, 0.0, 1.0],
+        uv: [1.0 - (x - 1.0).abs(), y],
+        tangent: [0.0; 4],
+    };
+    let vertices = [0.0, 1.0, 2.0]
+        .map(|x| [vertex(x, 0.0), vertex(x, 1.0)])
+        .concat();
+    let indices = [0, 2, 3, 0, 3, 1, 2, 4, 5, 2, 5, 3];
+    let (vertices, indices) = mesh_ops::tangents(&vertices, &indices);
+
+    // The seam vertices are split instead of averaging the opposite tangents.
+    assert_eq!(vertices.len(), 8);
+    for (triangle, expected) in indices.chunks_exact(3).zip(
+        [[1.0, 0.0, 0.0, 1.0], [-1.0, 0.0, 0.0, -1.0]]
+            .iter()
+            .flat_map(|t| [t, t]),
+    ) {
+        for &index in triangle {
+            assert_eq!(vertices[index as usize].tangent, *expected);
+        }
+    }
+}
+
+#[test]
+fn test_weld() {
+    let (vertices, indices) = grid(2);
+    let (flat, flat_indices) = mesh_ops::flat_normals(&vertices, &indices);
+    let (welded, welded_indices) = mesh_ops::weld(&flat, &flat_indices, 0.0);
+    assert_eq!(welded.len(), vertices.len());
+    for (&welded_index, &index) in welded_indices.iter().zip(&indices) {
+        assert_eq!(welded[welded_index as usize], vertices[index as usize]);
+    }
+
+    let mut noisy = flat.clone();
+    noisy[1].position[0] += 1e-5;
+    assert_eq!(mesh_ops::weld(&noisy, &flat_indices, 0.0).0.len(), 10);
+    assert_eq!(mesh_ops::weld(&noisy, &flat_indices, 1e-3).0.len(), 9);
+}
+
+#[test]
+fn test_vertex_cache() {
+    let (vertices, indices) = grid(32);
+    // Scatter the triangles so the original order has no locality.
+    let mut shuffled: Vec<u32> = (0..indices.len() / 3)
+        .map(|t| (t * 7919) % (indices.len() / 3))
+        .flat_map(|t| indices[t * 3..t * 3 + 3].to_vec())
+        .collect();
+    let before = mesh_ops::average_cache_miss_ratio(&shuffled, vertices.len(), 16);
+    mesh_ops::optimize_vertex_cache(&mut shuffled, vertices.len());
+    let after = mesh_ops::average_cache_miss_ratio(&shuffled, vertices.len(), 16);
+    assert_eq!(sorted_triangles(&shuffled), sorted_triangles(&indices));
+    assert!(before > 2.0, "{}", before);
+    assert!(after < 0.8, "{}", after);
+}
+
+/// Average number of fragments written per covered pixel, rendered with back-face culling
+/// and a depth test from the directions of the axes and the diagonals.
+fn overdraw(vertices: &[MeshVertex], indices: &[u32]) -> f32 {
+    const SIZE: usize = 64;
+    let (mut written, mut covered) = (0, 0);
+    let signs = [-1.0, 0.0, 1.0];
+    for x in signs {
+        for y in signs {
+            for z in signs {
+                if (x, y, z) == (0.0, 0.0, 0.0) {
+                    continue;
+                }
+                let view = normalize([x, y, z]);
+                let up = if view[1].abs() < 0.9 {
+                    [0.0, 1.0, 0.0]
+                } else {
+                    [1.0, 0.0, 0.0]
+                };
+                let right = normalize(cross(up, view));
+                let up = cross(view, right);
+                let project = |index: u32| {
+                    let p = vertices[index as usize].position;
+                    let to_pixel = |v: f32| (v * 0.45 + 0.5) * SIZE as f32;
+                    [to_pixel(dot(p, right)), to_pixel(dot(p, up)), dot(p, view)]
+                };
+                let mut depth = vec![f32::INFINITY; SIZE * SIZE];
+                for triangle in indices.chunks_exact(3) {
+                    let normal = triangle.iter().fold([0.0; 3], |sum, &index| {
+                        let n = vertices[index as usize].normal;
+                        [sum[0] + n[0], sum[1] + n[1], sum[2] + n[2]]
+                    });
+                    if dot(normal, view) >= 0.0 {
+                        continue;
+                    }
+                    let [a, b, c] = [0, 1, 2].map(|k| project(triangle[k]));
+                    let edge = |p: [f32; 3], q: [f32; 3], x: f32, y: f32| {
+                        (q[0] - p[0]) * (y - p[1]) - (q[1] - p[1]) * (x - p[0])
+                    };
+                    let area = edge(a, b, c[0], c[1]);
+                    if area == 0.0 {
+                        continue;
+                    }
+                    let range = |k: usize| {
+                        let min = a[k].min(b[k]).min(c[k]).max(0.0) as usize;
+                        let max = (a[k].max(b[k]).max(c[k]).ceil() as usize).min(SIZE);
+                        min..max
+                    };
+                    for py in range(1) {
+                        for px in range(0) {
+                            let (x, y) = (px as f32 + 0.5, py as f32 + 0.5);
+                            let (wa, wb, wc) = (
+                                edge(b, c, x, y) / area,
+                                edge(c, a, x, y) / area,
+                                edge(a, b, x, y) / area,
+                            );
+                            if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                                continue;
+                            }
+                            let z = wa * a[2] + wb * b[2] + wc * c[2];
+                            let pixel = &mut depth[py * SIZE + px];
+                            if z < *pixel {
+                                *pixel = z;
+                                written += 1;
+                            }
+                        }
+                    }
+                }
+                covered += depth.iter().filter(|depth| depth.is_finite()).count();
+            }
+        }
+    }
+    written as f32 / covered as f32
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let length = dot(a, a).sqrt();
+    [a[0] / length, a[1] / length, a[2] / length]
+}
+
+#[test]
+fn test_overdraw() {
+    // A sphere inside another one, whose triangles are hidden when the outer one is drawn first.
+    let mut sphere = shapes::uv_sphere(0.5, 32, 16);
+    let outer = shapes::uv_sphere(1.0, 32, 16);
+    let offset = sphere.vertices.len() as u32;
+    sphere.vertices.extend(&outer.vertices);
+    sphere
+        .indices
+        .extend(outer.indices.iter().map(|index| index + offset));
+
+    let mut indices = sphere.indices.clone();
+    mesh_ops::optimize_vertex_cache(&mut indices, sphere.vertices.len());
+    let cache_before = mesh_ops::average_cache_miss_ratio(&indices, sphere.vertices.len(), 16);
+    let overdraw_before = overdraw(&sphere.vertices, &indices);
+    mesh_ops::optimize_overdraw(&mut indices, &sphere.vertices, 1.05);
+    let cache_after = mesh_ops::average_cache_miss_ratio(&indices, sphere.vertices.len(), 16);
+    let overdraw_after = overdraw(&sphere.vertices, &indices);
+    assert_eq!(
+        sorted_triangles(&indices),
+        sorted_triangles(&sphere.indices)
+    );
+    assert!(
+        cache_after <= cache_before * 1.05 + 1e-4,
+        "{} {}",
+        cache_before,
+        cache_after
+    );
+    assert!(
+        overdraw_after < overdraw_before,
+        "{} {}",
+        overdraw_before,
+        overdraw_after
+    );
+
+    sphere.indices = indices;
+    let vertices = mesh_ops::optimize_vertex_fetch(&sphere.vertices, &mut sphere.indices);
+    assert_eq!(vertices.len(), sphere.vertices.len());
+}
+
+#[test]
+fn test_vertex_fetch() {
+    let vertices = [10, 11, 12, 13, 14];
+    let mut indices = [3, 1, 4, 4, 1, 3];
+    let fetched = mesh_ops::optimize_vertex_fetch(&vertices, &mut indices);
+    assert_eq!(fetched, [13, 11, 14]);
+    assert_eq!(indices, [0, 1, 2, 2, 1, 0]);
+}
@@ -0,0 +1,75 @@
+use opengl::*;
+
+const CUBE_FACE: &str = "
+mtllib cube.mtl
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+usemtl red
+f 1/1/1 2/2/1 3/3/1 4/4/1 # quad
+usemtl blue
+f -4/-4/-1 -2/-2/-1 -1/-1/-1
+usemtl red
+f 1//1 3//1 \\
+  4//1
+";
+
+#[test]
+fn test_parse_obj() {
+    let model = ObjModel::parse(CUBE_FACE).unwrap();
+    assert_eq!(model.material_libs, ["cube.mtl"]);
+    assert_eq!(model.vertices.len(), 7);
+    assert_eq!(model.indices, [0, 1, 2, 0, 2, 3, 4, 5, 6, 0, 2, 3]);
+    assert_eq!(model.groups.len(), 2);
+    assert_eq!(model.groups[0].material.as_deref(), Some("red"));
+    assert_eq!(model.groups[0].range, SubMesh { first: 0, count: 9 });
+    assert_eq!(model.groups[1].material.as_deref(), Some("blue"));
+    assert_eq!(model.groups[1].range, SubMesh { first: 9, count: 3 });
+    assert_eq!(
+        model.vertices[2],
+        MeshVertex {
+            position: [1.0, 1.0, 0.0],
+            normal: [0.0, 0.0, 1.0],
+            uv: [1.0, 1.0],
+            tangent: [0.0; 4],
+        }
+    );
+    assert_eq!(model.bounds().max, [1.0, 1.0, 0.0]);
+}
+
+#[test]
+fn test_parse_material_libs() {
+    let model = ObjModel::parse("mtllib a.mtl b.mtl\nmtllib c.mtl\n").unwrap();
+    assert_eq!(model.material_libs, ["a.mtl", "b.mtl", "c.mtl"]);
+}
+
+#[test]
+fn test_parse_obj_errors() {
+    assert!(ObjModel::parse("v 0 0 0\nf 1 2 3")
+        .unwrap_err()
+        .starts_with("Line 2"));
+    assert!(ObjModel::parse("v 0 0\n").is_err());
+    assert!(ObjModel::parse("v 0 0 0\nf 1 1").is_err());
+}
+
+#[test]
+fn test_parse_mtl() {
+    let materials = Material::parse_mtl(
+        "newmtl red\nKd 1 0 0\nNs 32\nTr 0.25\nmap_Kd -s 1 1 1 red.png\nbump red normal.png\n\nnewmtl blue\n",
+    )
+    .unwrap();
+    assert_eq!(materials.len(), 2);
+    assert_eq!(materials[0].diffuse, [1.0, 0.0, 0.0]);
+    assert_eq!(materials[0].shininess, 32.0);
+    assert_eq!(materials[0].opacity, 0.75);
+    assert_eq!(materials[0].diffuse_map.as_deref(), Some("red.png"));
+    assert_eq!(materials[0].normal_map.as_deref(), Some("red normal.png"));
+    assert_eq!(materials[1], Material::new("blue"));
+    assert!(Material::parse_mtl("Kd 1 1 1").is_err());
+}
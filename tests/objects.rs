@@ -0,0 +1,85 @@
+use std::cell::RefCell;
+
+use opengl::*;
+
+thread_local! {
+    /// Calls made on `Fake` objects: `(call, ids)`.
+    static CALLS: RefCell<Vec<(&'static str, Vec<u32>)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn take_calls() -> Vec<(&'static str, Vec<u32>)> {
+    CALLS.with(|calls| calls.take())
+}
+
+fn record(call: &'static str, ids: &[u32]) {
+    CALLS.with(|calls| calls.borrow_mut().push((call, ids.to_vec())));
+}
+
+/// Object which only records its calls, so no OpenGL context is needed.
+struct Fake {
+    id: u32,
+}
+
+impl GlObject for Fake {
+    type Target = u32;
+
+    unsafe fn create(target: u32, ids: &mut [u32]) {
+        for (i, id) in ids.iter_mut().enumerate() {
+            *id = target + i as u32;
+        }
+        record("create", ids);
+    }
+
+    unsafe fn delete(ids: &[u32]) {
+        record("delete", ids);
+    }
+
+    unsafe fn from_id(id: u32) -> Self {
+        Self { id }
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl Drop for Fake {
+    fn drop(&mut self) {
+        record("drop", &[self.id]);
+    }
+}
+
+#[test]
+fn test_objects_deleted_by_one_call() {
+    let mut objects = Objects::<Fake>::with_target(10, 3);
+    assert_eq!(take_calls(), [("create", vec![10, 11, 12])]);
+    assert_eq!(objects.count(), 3);
+    assert_eq!(objects.ids(), [10, 11, 12]);
+    assert_eq!(objects[1].id(), 11);
+    assert_eq!(objects.iter().map(Fake::id).sum::<u32>(), 33);
+    objects[2].id = 20;
+    assert_eq!((&objects).into_iter().last().map(Fake::id), Some(20));
+
+    // One call deletes every object, their own `Drop` does not run.
+    drop(objects);
+    assert_eq!(take_calls(), [("delete", vec![10, 11, 20])]);
+}
+
+#[test]
+fn test_objects_into_iter() {
+    let objects = Objects::<Fake>::with_target(1, 2);
+    take_calls();
+    let mut iter = objects.into_iter();
+    let first = iter.next().unwrap();
+    assert_eq!(first.id(), 1);
+    // Each object yielded is dropped on its own, the others with the iterator.
+    drop(iter);
+    drop(first);
+    assert_eq!(take_calls(), [("drop", vec![2]), ("drop", vec![1])]);
+}
+
+#[test]
+#[should_panic(expected = "The number of objects must be greater than 0")]
+fn test_objects_empty() {
+    Objects::<Fake>::with_target(0, 0);
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use opengl::*;
+
+fn files(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|&(path, code)| (path.to_owned(), code.to_owned()))
+        .collect()
+}
+
+#[test]
+fn test_include() {
+    let provider = files(&[
+        (
+            "shaders/main.frag",
+            "#version 450 core\n#include \"lib/light.glsl\"\nout vec4 color;\n#include </common.glsl>\nvoid main() {}\n",
+        ),
+        (
+            "shaders/lib/light.glsl",
+            "#version 450 core\n#include \"../../common.glsl\"\nvec3 light() { return vec3(PI); }\n",
+        ),
+        ("common.glsl", "#pragma once\nconst float PI = 3.14159;\n"),
+    ]);
+    let output = Preprocessor::new(provider)
+        .process("shaders/main.frag")
+        .unwrap();
+    assert_eq!(
+        output.files,
+        ["shaders/main.frag", "shaders/lib/light.glsl", "common.glsl"]
+    );
+    assert_eq!(
+        output.code,
+        "#version 450 core\n\
+         #line 1 1\n\
+         \n\
+         #line 1 2\n\
+         \n\
+         const float PI = 3.14159;\n\
+         #line 3 1\n\
+         vec3 light() { return vec3(PI); }\n\
+         #line 3 0\n\
+         out vec4 color;\n\
+         \n\
+         void main() {}\n"
+    );
+    assert_eq!(output.origin(1), Some(("shaders/main.frag", 1)));
+    assert_eq!(output.origin(6), Some(("common.glsl", 2)));
+    assert_eq!(output.origin(8), Some(("shaders/lib/light.glsl", 3)));
+    assert_eq!(output.origin(12), Some(("shaders/main.frag", 5)));
+    assert_eq!(output.origin(13), None);
+    assert_eq!(
+        output.sources()[2],
+        "#pragma once\nconst float PI = 3.14159;\n"
+    );
+}
+
+#[test]
+fn test_include_errors() {
+    let provider = files(&[
+        ("a.glsl", "#include \"b.glsl\"\n"),
+        ("b.glsl", "\n#include \"a.glsl\"\n"),
+        ("c.glsl", "void f();\n#include \"missing.glsl\"\n"),
+    ]);
+    let preprocessor = Preprocessor::new(provider);
+    assert_eq!(
+        preprocessor.process("a.glsl").unwrap_err(),
+        "b.glsl:2: Include cycle a.glsl -> b.glsl -> a.glsl"
+    );
+    assert_eq!(
+        preprocessor.process("c.glsl").unwrap_err(),
+        "c.glsl:2: File 'missing.glsl' not found"
+    );
+}
+
+#[test]
+fn test_include_outside_root() {
+    let provider = files(&[
+        ("lib/a.glsl", "#include \"../../secret\"\n"),
+        ("b.glsl", "#include \"/../secret\"\n"),
+    ]);
+    let preprocessor = Preprocessor::new(provider);
+    assert_eq!(
+        preprocessor.process("lib/a.glsl").unwrap_err(),
+        "lib/a.glsl:1: Include '../../secret' is outside of the root"
+    );
+    assert_eq!(
+        preprocessor.process("b.glsl").unwrap_err(),
+        "b.glsl:1: Include '/../secret' is outside of the root"
+    );
+    assert!(preprocessor.process("../b.glsl").is_err());
+
+    let directory = DirectoryProvider::new(".");
+    assert_eq!(
+        directory.read("../secret").unwrap_err(),
+        "Path '../secret' is outside of the root"
+    );
+}
+
+#[test]
+fn test_recursive_pragma_once() {
+    let provider = files(&[
+        ("main.glsl", "#include \"a.glsl\"\nvoid main() {}\n"),
+        ("a.glsl", "#pragma once\n#include \"b.glsl\"\nvoid a();\n"),
+        ("b.glsl", "#include \"a.glsl\"\nvoid b();\n"),
+    ]);
+    let output = Preprocessor::new(provider).process("main.glsl").unwrap();
+    assert_eq!(output.files, ["main.glsl", "a.glsl", "b.glsl"]);
+    assert_eq!(output.code.matches("void a();").count(), 1);
+    assert_eq!(output.code.matches("void b();").count(), 1);
+}
+
+#[test]
+fn test_render_included_diagnostic() {
+    let provider = files(&[
+        ("main.vert", "#version 450 core\n#include \"util.glsl\"\n"),
+        ("util.glsl", "float f() {\n    return x;\n}\n"),
+    ]);
+    let output = Preprocessor::new(provider).process("main.vert").unwrap();
+    let diagnostics = Diagnostic::parse("0:2(12): error: `x' undeclared\n");
+    let mut diagnostic = diagnostics[0].clone();
+    diagnostic.source = 1;
+    assert_eq!(
+        output.render(&diagnostic),
+        "error: `x' undeclared\n --> util.glsl:2:12\n  |\n2 |     return x;\n  |            ^"
+    );
+}
@@ -0,0 +1,59 @@
+use opengl::*;
+
+const VS: &str = "#version 450 core\nvoid main() {}\n";
+const FS: &str = "#version 450 core\nout vec4 color;\nvoid main() { color = vec4(1.0); }\n";
+
+#[test]
+fn test_key() {
+    let cache = ProgramCache::with_driver("cache", "Vendor\nRenderer\n4.5.0");
+    let stages = [(ShaderType::Vertex, VS), (ShaderType::Fragmet, FS)];
+    let key = cache.key(&stages, &["SKINNED", "SHADOWS"]);
+    // The hash must not change between runs and releases.
+    assert_eq!(key, 0x4e46_4063_0170_a827);
+    assert_eq!(key, cache.key(&stages, &["SHADOWS", "SKINNED", "SKINNED"]));
+    assert_ne!(key, cache.key(&stages, &["SKINNED"]));
+    assert_ne!(
+        key,
+        cache.key(
+            &[(ShaderType::Fragmet, VS), (ShaderType::Fragmet, FS)],
+            &["SKINNED", "SHADOWS"]
+        )
+    );
+    // The length prefix keeps the boundaries between strings.
+    assert_ne!(
+        cache.key(&[(ShaderType::Vertex, "ab")], &["c"]),
+        cache.key(&[(ShaderType::Vertex, "a")], &["bc"])
+    );
+    let other = ProgramCache::with_driver("cache", "Vendor\nRenderer\n4.6.0");
+    assert_ne!(key, other.key(&stages, &["SKINNED", "SHADOWS"]));
+    assert_eq!(
+        cache.path(0x1234),
+        std::path::Path::new("cache").join("0000000000001234.bin")
+    );
+}
+
+#[test]
+fn test_store_binary() {
+    let directory = std::env::temp_dir().join(format!("opengl-cache-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    let cache = ProgramCache::with_driver(&directory, "Vendor\nRenderer\n4.5.0");
+    let data: Vec<u8> = (0..=255).collect();
+    cache.store_binary(7, 0x8741, &data).unwrap();
+    assert_eq!(cache.load_binary(7), Some((0x8741, data.clone())));
+    // Only the final file is left, the temporary one was renamed.
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+
+    // A truncated file is rejected and removed.
+    let bytes = std::fs::read(cache.path(7)).unwrap();
+    std::fs::write(cache.path(7), &bytes[..bytes.len() - 1]).unwrap();
+    assert_eq!(cache.load_binary(7), None);
+    assert!(!cache.path(7).exists());
+
+    // So is a corrupted one.
+    let mut bytes = bytes;
+    *bytes.last_mut().unwrap() ^= 1;
+    std::fs::write(cache.path(7), &bytes).unwrap();
+    assert_eq!(cache.load_binary(7), None);
+    assert_eq!(cache.load_binary(8), None);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
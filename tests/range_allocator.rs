@@ -0,0 +1,82 @@
+use opengl::*;
+
+#[test]
+fn test_alloc_and_coalesce() {
+    let mut ranges = RangeAllocator::new(1024);
+    let a = ranges.alloc(100, 4).unwrap();
+    let b = ranges.alloc(100, 4).unwrap();
+    let c = ranges.alloc(100, 4).unwrap();
+    assert_eq!(a.offset, 0);
+    assert_eq!(b.offset, 100);
+    assert_eq!(c.offset, 200);
+
+    ranges.free(a);
+    ranges.free(c);
+    assert_eq!(ranges.free_ranges(), &[0..100, 200..1024]);
+
+    ranges.free(b);
+    assert_eq!(ranges.free_ranges().len(), 1);
+    assert_eq!(ranges.free_ranges()[0], 0..1024);
+    assert_eq!(ranges.free_size(), 1024);
+}
+
+#[test]
+fn test_alignment() {
+    let mut ranges = RangeAllocator::new(256);
+    let a = ranges.alloc(10, 1).unwrap();
+    let b = ranges.alloc(24, 12).unwrap();
+    assert_eq!(a.offset, 0);
+    assert_eq!(b.offset, 12);
+    assert_eq!(b.first(12), 1);
+    assert_eq!(b.count(12), 2);
+    assert_eq!(ranges.free_ranges(), &[10..12, 36..256]);
+}
+
+#[test]
+fn test_exhaustion_and_grow() {
+    let mut ranges = RangeAllocator::new(64);
+    let a = ranges.alloc(64, 16).unwrap();
+    assert!(ranges.alloc(1, 1).is_none());
+
+    ranges.grow(128);
+    let b = ranges.alloc(64, 16).unwrap();
+    assert_eq!(b.offset, 64);
+
+    ranges.free(a);
+    ranges.free(b);
+    assert_eq!(ranges.largest_free(), 128);
+}
+
+#[test]
+fn test_empty_alloc() {
+    let mut ranges = RangeAllocator::new(64);
+    let a = ranges.alloc(64, 16).unwrap();
+    // Empty allocations take no space, even when the allocator is full.
+    let empty = ranges.alloc(0, 16).unwrap();
+    assert_eq!(empty.size, 0);
+    assert!(empty.range().is_empty());
+    assert!(ranges.free_ranges().is_empty());
+
+    ranges.free(empty);
+    ranges.free(a);
+    assert_eq!(ranges.largest_free(), 64);
+}
+
+#[test]
+#[should_panic(expected = "The slice overlaps a free range")]
+fn test_double_free() {
+    let mut ranges = RangeAllocator::new(64);
+    let a = ranges.alloc(16, 4).unwrap();
+    let _b = ranges.alloc(16, 4).unwrap();
+    ranges.free(a);
+    ranges.free(a);
+}
+
+#[test]
+fn test_oversized_alloc() {
+    let mut ranges = RangeAllocator::new(64);
+    let _a = ranges.alloc(8, 1).unwrap();
+    assert!(ranges.alloc(usize::MAX, 16).is_none());
+    assert_eq!(ranges.free_size(), 56);
+    assert_eq!(ranges.largest_free(), 56);
+}
@@ -0,0 +1,37 @@
+use opengl::*;
+
+#[test]
+fn test_insert_defines() {
+    let code = "// header\n#version 450 core\nvoid main() {}\n";
+    assert_eq!(
+        ShaderLibrary::insert_defines(code, &["SKINNED", "LIGHTS = 4"]),
+        "// header\n#version 450 core\n#define SKINNED\n#define LIGHTS 4\n#line 3 0\nvoid main() {}\n"
+    );
+    assert_eq!(
+        ShaderLibrary::insert_defines("void main() {}", &["SHADOWS"]),
+        "#define SHADOWS\n#line 1 0\nvoid main() {}"
+    );
+    assert_eq!(
+        ShaderLibrary::insert_defines("#version 450", &["A=1"]),
+        "#version 450\n#define A 1\n#line 2 0\n"
+    );
+    assert_eq!(ShaderLibrary::insert_defines(code, &[]), code);
+}
+
+#[test]
+fn test_variant_key() {
+    let key = ShaderLibrary::variant_key(&["SKINNED", "LIGHTS=4", "SHADOWS"]);
+    assert_eq!(key, ["LIGHTS=4", "SHADOWS", "SKINNED"]);
+    // The order and repeated defines do not matter.
+    assert_eq!(
+        ShaderLibrary::variant_key(&["SHADOWS", "SKINNED", "LIGHTS=4", "SKINNED"]),
+        key
+    );
+    // Neither do the spaces around the name and the value.
+    assert_eq!(
+        ShaderLibrary::variant_key(&[" SKINNED", "LIGHTS = 4", "SHADOWS "]),
+        key
+    );
+    assert_ne!(ShaderLibrary::variant_key(&["LIGHTS=8"]), ["LIGHTS=4"]);
+    assert!(ShaderLibrary::variant_key(&[]).is_empty());
+}
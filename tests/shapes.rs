@@ -0,0 +1,103 @@
+use opengl::shapes::{self, Shape};
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Check the indices, the unit normals and tangents, and that every triangle faces along its normals.
+fn check(shape: &Shape) {
+    assert_eq!(shape.indices.len() % 3, 0);
+    assert!(shape
+        .indices
+        .iter()
+        .all(|&i| (i as usize) < shape.vertices.len()));
+    for vertex in &shape.vertices {
+        let tangent = [vertex.tangent[0], vertex.tangent[1], vertex.tangent[2]];
+        assert!((dot(vertex.normal, vertex.normal) - 1.0).abs() < 1e-4);
+        assert!((dot(tangent, tangent) - 1.0).abs() < 1e-4);
+        assert!(dot(vertex.normal, tangent).abs() < 1e-4);
+        assert_eq!(vertex.tangent[3], 1.0);
+    }
+    for triangle in shape.indices.chunks(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| shape.vertices[triangle[k] as usize]);
+        let face = cross(sub(b.position, a.position), sub(c.position, a.position));
+        if dot(face, face) < 1e-12 {
+            continue;
+        }
+        for vertex in [a, b, c] {
+            assert!(dot(face, vertex.normal) > 0.0, "{:?}", triangle);
+        }
+    }
+}
+
+#[test]
+fn test_plane_and_cube() {
+    let plane = shapes::plane(2.0, 4.0, 2, 3);
+    check(&plane);
+    assert_eq!(plane.vertices.len(), 12);
+    assert_eq!(plane.indices.len(), 36);
+    assert_eq!(plane.bounds().size(), [2.0, 0.0, 4.0]);
+
+    let cube = shapes::cube(2.0, 2);
+    check(&cube);
+    assert_eq!(cube.vertices.len(), 6 * 9);
+    assert_eq!(cube.bounds().min, [-1.0; 3]);
+    assert_eq!(cube.bounds().max, [1.0; 3]);
+}
+
+#[test]
+fn test_spheres() {
+    let sphere = shapes::uv_sphere(1.0, 16, 8);
+    check(&sphere);
+    assert_eq!(sphere.vertices.len(), 17 * 9);
+
+    let icosphere = shapes::icosphere(2.0, 2);
+    check(&icosphere);
+    assert_eq!(icosphere.indices.len(), 20 * 16 * 3);
+    for vertex in &icosphere.vertices {
+        assert!((dot(vertex.position, vertex.position) - 4.0).abs() < 1e-4);
+    }
+    // No triangle stretches the texture across the seam.
+    for triangle in icosphere.indices.chunks(3) {
+        let us = triangle
+            .iter()
+            .map(|&i| icosphere.vertices[i as usize].uv[0]);
+        let (min, max) = us.fold((f32::MAX, f32::MIN), |(min, max), u| {
+            (min.min(u), max.max(u))
+        });
+        assert!(max - min <= 0.5);
+    }
+}
+
+#[test]
+fn test_solids() {
+    check(&shapes::cylinder(1.0, 2.0, 12, 2));
+    check(&shapes::cone(1.0, 2.0, 12, 3));
+    check(&shapes::torus(2.0, 0.5, 24, 12));
+
+    let capsule = shapes::capsule(0.5, 1.0, 12, 4);
+    check(&capsule);
+    assert_eq!(capsule.bounds().size()[1], 2.0);
+    let vs = capsule.vertices.iter().map(|vertex| vertex.uv[1]);
+    assert_eq!(vs.clone().fold(f32::MAX, f32::min), 0.0);
+    assert!((vs.fold(f32::MIN, f32::max) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_fullscreen_triangle() {
+    let triangle = shapes::fullscreen_triangle();
+    check(&triangle);
+    assert_eq!(triangle.indices, [0, 1, 2]);
+}
@@ -0,0 +1,132 @@
+use opengl::mats::{Mat, Mat3, Vec2, Vec3, Vec4};
+use opengl::*;
+
+#[derive(Std140, Std430)]
+struct Light {
+    position: Vec3<f32>,
+    intensity: f32,
+    color: Vec3<f32>,
+}
+
+#[derive(Std140, Std430)]
+struct Scene {
+    scale: f32,
+    offset: Vec2<f32>,
+    normal: Mat3<f32>,
+    weights: [f32; 3],
+    lights: [Light; 2],
+    enabled: bool,
+}
+
+fn floats(bytes: &[u8], offset: usize, count: usize) -> Vec<f32> {
+    bytes[offset..offset + count * 4]
+        .chunks(4)
+        .map(|chunk| f32::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+fn scene() -> Scene {
+    let light = |x: f32| Light {
+        position: Vec3::from([[x, x + 1.0, x + 2.0]]),
+        intensity: x + 3.0,
+        color: Vec3::from([[x + 4.0, x + 5.0, x + 6.0]]),
+    };
+    Scene {
+        scale: 2.0,
+        offset: Vec2::from([[3.0, 4.0]]),
+        normal: Mat3::from([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]),
+        weights: [0.5, 0.25, 0.125],
+        lights: [light(10.0), light(20.0)],
+        enabled: true,
+    }
+}
+
+#[test]
+fn test_scalars_and_vectors() {
+    assert_eq!((<f32 as Std140>::ALIGN, <f32 as Std140>::SIZE), (4, 4));
+    assert_eq!((<f64 as Std140>::ALIGN, <f64 as Std140>::SIZE), (8, 8));
+    assert_eq!(
+        (<Vec2<f32> as Std140>::ALIGN, <Vec2<f32> as Std140>::SIZE),
+        (8, 8)
+    );
+    assert_eq!(
+        (<Vec3<f32> as Std140>::ALIGN, <Vec3<f32> as Std140>::SIZE),
+        (16, 12)
+    );
+    assert_eq!(
+        (<Vec4<f64> as Std140>::ALIGN, <Vec4<f64> as Std140>::SIZE),
+        (32, 32)
+    );
+    assert_eq!(true.to_std140(), 1u32.to_ne_bytes());
+}
+
+#[test]
+fn test_bool_vectors() {
+    // A `bvecN` has `N` components of 4 bytes, like a `uvecN`.
+    assert_eq!(
+        (<Vec3<bool> as Std140>::ALIGN, <Vec3<bool> as Std140>::SIZE),
+        (16, 12)
+    );
+    assert_eq!(
+        (<Vec2<bool> as Std430>::ALIGN, <Vec2<bool> as Std430>::SIZE),
+        (8, 8)
+    );
+    let bytes = Vec3::from([[true, false, true]]).to_std140();
+    assert_eq!(bytes, Vec3::from([[1u32, 0, 1]]).to_std140());
+    let bytes = Vec4::from([[false, true, true, false]]).to_std430();
+    assert_eq!(bytes, Vec4::from([[0u32, 1, 1, 0]]).to_std430());
+}
+
+#[test]
+fn test_arrays_and_matrices() {
+    assert_eq!(<[f32; 3] as Std140>::SIZE, 48);
+    assert_eq!(<[f32; 3] as Std430>::SIZE, 12);
+    assert_eq!(<[Vec3<f32>; 2] as Std430>::SIZE, 32);
+    assert_eq!(<Mat<f32, 2, 2> as Std140>::SIZE, 32);
+    assert_eq!(<Mat<f32, 2, 2> as Std430>::SIZE, 16);
+    // A `mat2x3` has 2 columns of 3 rows.
+    assert_eq!(<Mat<f32, 3, 2> as Std140>::SIZE, 32);
+    assert_eq!(<Mat<f32, 2, 3> as Std430>::SIZE, 24);
+
+    let matrix = Mat3::from([[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+    let bytes = matrix.to_std140();
+    assert_eq!(floats(&bytes, 0, 3), [1.0, 4.0, 7.0]);
+    assert_eq!(floats(&bytes, 16, 3), [2.0, 5.0, 8.0]);
+    assert_eq!(floats(&bytes, 32, 3), [3.0, 6.0, 9.0]);
+}
+
+#[test]
+fn test_derive_std140() {
+    assert_eq!(<Light as Std140>::ALIGN, 16);
+    assert_eq!(<Light as Std140>::SIZE, 32);
+    assert_eq!(<Scene as Std140>::SIZE, 192);
+
+    let bytes = scene().to_std140();
+    assert_eq!(bytes.len(), 192);
+    assert_eq!(floats(&bytes, 0, 1), [2.0]);
+    assert_eq!(floats(&bytes, 8, 2), [3.0, 4.0]);
+    assert_eq!(floats(&bytes, 16, 3), [1.0, 4.0, 7.0]);
+    assert_eq!(floats(&bytes, 64, 1), [0.5]);
+    assert_eq!(floats(&bytes, 80, 1), [0.25]);
+    assert_eq!(floats(&bytes, 96, 1), [0.125]);
+    assert_eq!(floats(&bytes, 112, 4), [10.0, 11.0, 12.0, 13.0]);
+    assert_eq!(floats(&bytes, 128, 3), [14.0, 15.0, 16.0]);
+    assert_eq!(floats(&bytes, 144, 4), [20.0, 21.0, 22.0, 23.0]);
+    assert_eq!(bytes[176..180], 1u32.to_ne_bytes());
+    assert!(bytes[180..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn test_derive_std430() {
+    assert_eq!(<Light as Std430>::ALIGN, 16);
+    assert_eq!(<Light as Std430>::SIZE, 32);
+    assert_eq!(<Scene as Std430>::SIZE, 160);
+
+    let bytes = scene().to_std430();
+    assert_eq!(floats(&bytes, 8, 2), [3.0, 4.0]);
+    assert_eq!(floats(&bytes, 16, 3), [1.0, 4.0, 7.0]);
+    assert_eq!(floats(&bytes, 64, 3), [0.5, 0.25, 0.125]);
+    assert_eq!(floats(&bytes, 80, 4), [10.0, 11.0, 12.0, 13.0]);
+    assert_eq!(floats(&bytes, 112, 4), [20.0, 21.0, 22.0, 23.0]);
+    assert_eq!(bytes[144..148], 1u32.to_ne_bytes());
+}
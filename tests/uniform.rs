@@ -0,0 +1,17 @@
+use opengl::*;
+
+#[test]
+fn test_accepts_opaque() {
+    assert!(<i32 as UniformValue>::accepts(GlslType::Sampler2D));
+    assert!(<i32 as UniformValue>::accepts(GlslType::UImage2D));
+    // `GL_SAMPLER_2D_RECT` and `GL_INT_IMAGE_CUBE` have no variant of their own.
+    assert!(<i32 as UniformValue>::accepts(GlslType::Other(0x8b63)));
+    assert!(<i32 as UniformValue>::accepts(GlslType::Other(0x905b)));
+    assert!(<i32 as UniformValue>::accepts(GlslType::Int));
+
+    assert!(!<i32 as UniformValue>::accepts(GlslType::AtomicUInt));
+    assert!(!<i32 as UniformValue>::accepts(GlslType::Vec3));
+    // Any other unknown type, e.g. `GL_FLOAT16_NV`.
+    assert!(!<i32 as UniformValue>::accepts(GlslType::Other(0x8ff8)));
+    assert!(!<u32 as UniformValue>::accepts(GlslType::Sampler2D));
+}
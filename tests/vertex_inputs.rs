@@ -0,0 +1,112 @@
+use opengl::*;
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+struct Vertex {
+    #[vertex(name = "aPos")]
+    position: [f32; 3],
+    #[vertex(name = "aColor", normalized)]
+    color: [u8; 4],
+    #[vertex(name = "aBone")]
+    bone: u32,
+}
+
+fn input(name: &str, ty: GlslType, location: i32) -> ProgramVariable {
+    ProgramVariable {
+        name: name.to_string(),
+        ty,
+        array_size: 1,
+        location,
+    }
+}
+
+#[test]
+fn test_validate_ok() {
+    let inputs = VertexInputs::from_variables(vec![
+        input("aPos", GlslType::Vec4, 0),
+        input("aColor", GlslType::Vec4, 1),
+        input("aBone", GlslType::UInt, 2),
+    ]);
+    assert_eq!(inputs.validate_layout::<Vertex>(), Ok(()));
+}
+
+#[test]
+fn test_validate_errors() {
+    let inputs = VertexInputs::from_variables(vec![
+        input("aPos", GlslType::Vec3, 3),
+        input("aBone", GlslType::IVec4, 2),
+        input("aNormal", GlslType::Vec3, 4),
+    ]);
+    let errors = inputs.validate_layout::<Vertex>().unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            LayoutError::TypeMismatch {
+                name: "aBone".to_string(),
+                expected: GlslType::IVec4,
+                found: (GlType::u32, 1, AttribKind::Integer),
+            },
+            LayoutError::Missing {
+                name: "aNormal".to_string(),
+                ty: GlslType::Vec3,
+            },
+            LayoutError::LocationMismatch {
+                name: "aPos".to_string(),
+                expected: 3,
+                found: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_assign_locations() {
+    let inputs = VertexInputs::from_variables(vec![
+        input("aColor", GlslType::Vec4, 0),
+        input("aPos", GlslType::Vec3, 5),
+    ]);
+    let assigned = inputs.assign(Vertex::ATTRIBUTES).unwrap();
+    let locations: Vec<_> = assigned.iter().map(|a| (a.name, a.location)).collect();
+    assert_eq!(locations, [("aColor", 0), ("aPos", 5)]);
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+struct Precise {
+    #[vertex(name = "aPos")]
+    position: [f64; 3],
+    #[vertex(name = "aTransform")]
+    #[vertex(transposed)]
+    transform: mats::Mat4<f64>,
+    #[vertex(name = "aWeight")]
+    weight: f64,
+}
+
+#[test]
+fn test_validate_doubles() {
+    assert_eq!(GlslType::DVec3.locations(), 2);
+    assert_eq!(GlslType::DMat4.locations(), 8);
+    assert_eq!(GlslType::DMat3x2.locations(), 3);
+    let inputs = VertexInputs::from_variables(vec![
+        input("aPos", GlslType::DVec3, 0),
+        input("aTransform", GlslType::DMat4, 2),
+        input("aWeight", GlslType::Double, 10),
+    ]);
+    assert_eq!(inputs.validate_layout::<Precise>(), Ok(()));
+
+    // A `dvec2` takes one location, unlike the `[f64; 3]` attribute.
+    let inputs = VertexInputs::from_variables(vec![
+        input("aPos", GlslType::DVec2, 0),
+        input("aTransform", GlslType::DMat4, 2),
+        input("aWeight", GlslType::Double, 10),
+    ]);
+    let errors = inputs.validate_layout::<Precise>().unwrap_err();
+    assert_eq!(
+        errors,
+        [LayoutError::TypeMismatch {
+            name: "aPos".to_string(),
+            expected: GlslType::DVec2,
+            found: (GlType::f64, 3, AttribKind::Double),
+        }]
+    );
+}
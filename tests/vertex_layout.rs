@@ -0,0 +1,208 @@
+use opengl::*;
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+struct Vertex {
+    position: [f32; 3],
+    #[vertex(normalized)]
+    color: [u8; 4],
+    #[vertex(location = 4)]
+    uv: mats::Vec2<f32>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+struct Padded {
+    value: f32,
+    #[vertex(skip)]
+    _padding: [f32; 3],
+    #[vertex(location = 1)]
+    index: u16,
+}
+
+#[test]
+fn test_derive_layout() {
+    assert_eq!(Vertex::STRIDE, 24);
+    assert_eq!(
+        Vertex::ATTRIBUTES,
+        &[
+            VertexAttribute {
+                name: "position",
+                location: 0,
+                locations: 1,
+                ty: GlType::f32,
+                count: 3,
+                kind: AttribKind::Float,
+                offset: 0,
+                divisor: 0,
+            },
+            VertexAttribute {
+                name: "color",
+                location: 1,
+                locations: 1,
+                ty: GlType::u8,
+                count: 4,
+                kind: AttribKind::Normalized,
+                offset: 12,
+                divisor: 0,
+            },
+            VertexAttribute {
+                name: "uv",
+                location: 4,
+                locations: 1,
+                ty: GlType::f32,
+                count: 2,
+                kind: AttribKind::Float,
+                offset: 16,
+                divisor: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_derive_skip() {
+    let attributes = Padded::ATTRIBUTES;
+    assert_eq!(attributes.len(), 2);
+    assert_eq!(attributes[1].name, "index");
+    assert_eq!(attributes[1].ty, GlType::u16);
+    assert_eq!(attributes[1].kind, AttribKind::Integer);
+    assert_eq!(attributes[1].offset, 16);
+    assert_eq!(Padded::STRIDE, 20);
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+struct Kinds {
+    id: u32,
+    #[vertex(float)]
+    weight: u16,
+    precise: [f64; 2],
+}
+
+#[test]
+fn test_derive_kind() {
+    let kinds: Vec<_> = Kinds::ATTRIBUTES.iter().map(|a| a.kind).collect();
+    assert_eq!(
+        kinds,
+        [AttribKind::Integer, AttribKind::Float, AttribKind::Double]
+    );
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+struct Doubles {
+    position: [f64; 3],
+    uv: [f64; 2],
+    #[vertex(transposed)]
+    transform: mats::Mat4<f64>,
+    id: u32,
+}
+
+#[test]
+fn test_derive_doubles() {
+    let [position, uv, transform, id] = Doubles::ATTRIBUTES else {
+        panic!("Expected four attributes");
+    };
+    assert_eq!((position.location, position.locations), (0, 2));
+    assert_eq!(position.slots().collect::<Vec<_>>(), [(0, 0)]);
+    assert_eq!((uv.location, uv.locations), (2, 1));
+    assert_eq!((transform.location, transform.locations), (3, 8));
+    assert_eq!(
+        transform.slots().collect::<Vec<_>>(),
+        [(3, 40), (5, 72), (7, 104), (9, 136)]
+    );
+    assert_eq!(id.location, 11);
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+#[vertex(divisor = 1)]
+struct Instance {
+    #[vertex(location = 3, transposed)]
+    transform: mats::Mat4<f32>,
+    #[vertex(divisor = 2)]
+    tint: [f32; 4],
+}
+
+#[test]
+fn test_derive_instanced() {
+    let [transform, tint] = Instance::ATTRIBUTES else {
+        panic!("Expected two attributes");
+    };
+    assert_eq!(transform.location, 3);
+    assert_eq!(transform.locations, 4);
+    assert_eq!(transform.count, 4);
+    assert_eq!(transform.divisor, 1);
+    assert_eq!(
+        transform.slots().collect::<Vec<_>>(),
+        [(3, 0), (4, 16), (5, 32), (6, 48)]
+    );
+    assert_eq!(tint.location, 7);
+    assert_eq!(tint.offset, 64);
+    assert_eq!(tint.divisor, 2);
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+#[vertex(divisor = 1)]
+struct Model {
+    /// The columns of a GLSL `mat4`, no transposition needed.
+    model: [[f32; 4]; 4],
+}
+
+#[test]
+fn test_derive_columns() {
+    let [model] = Model::ATTRIBUTES else {
+        panic!("Expected one attribute");
+    };
+    assert_eq!((model.locations, model.count), (4, 4));
+    assert_eq!(
+        model.slots().collect::<Vec<_>>(),
+        [(0, 0), (1, 16), (2, 32), (3, 48)]
+    );
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, VertexLayout)]
+struct Packed {
+    position: [Fixed; 3],
+    normal: Int2_10_10_10Rev,
+    color: UnsignedInt10F11F11FRev,
+}
+
+#[test]
+fn test_packed_attributes() {
+    let [position, normal, color] = Packed::ATTRIBUTES else {
+        panic!("Expected three attributes");
+    };
+    assert_eq!((position.ty, position.count), (GlType::Fixed, 3));
+    assert_eq!((normal.ty, normal.count), (GlType::Int2_10_10_10Rev, 4));
+    assert_eq!(normal.kind, AttribKind::Normalized);
+    assert_eq!(normal.offset, 12);
+    assert_eq!(GlType::Int2_10_10_10Rev.attrib_size(4), 4);
+    assert_eq!(
+        (color.ty, color.count),
+        (GlType::UnsignedInt10F11F11FRev, 3)
+    );
+    assert_eq!(Packed::STRIDE, 20);
+
+    let packed = Int2_10_10_10Rev::from_normalized([1.0, -1.0, 0.0, 1.0]);
+    assert_eq!(packed.0 & 0x3ff, 511);
+    assert_eq!((packed.0 >> 10) & 0x3ff, 0x201);
+    assert_eq!(packed.0 >> 30, 1);
+    assert_eq!(Fixed::from_f32(1.5).to_f32(), 1.5);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn test_half_attributes() {
+    #[repr(C)]
+    #[derive(Clone, Copy, VertexLayout)]
+    struct Half {
+        uv: [half::f16; 2],
+    }
+
+    assert_eq!(Half::ATTRIBUTES[0].ty, GlType::HalfFloat);
+    assert_eq!(Half::STRIDE, 4);
+}